@@ -0,0 +1,32 @@
+extern crate sctp;
+use sctp::*;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    // Create a new one-to-many endpoint
+    let endpoint = SctpEndpoint::bind("127.0.0.1:3868").unwrap();
+    println!("Bound to {:?}", endpoint.local_addrs().unwrap());
+
+    // Log the lifecycle of the associations as they come and go
+    let mut notifications = endpoint.notification_stream().unwrap();
+    while let Some(incoming) = notifications.next().await {
+        match incoming {
+            Ok(IncomingMessage::Notification(SctpNotification::AssocChange {
+                assoc_id,
+                state,
+                ..
+            })) => println!("Association {}: {:?}", assoc_id, state),
+            Ok(IncomingMessage::Notification(SctpNotification::PeerAddrChange {
+                assoc_id,
+                addr,
+                state,
+                ..
+            })) => println!("Association {}: path {} is {:?}", assoc_id, addr, state),
+            Ok(IncomingMessage::Notification(other)) => println!("{:?}", other),
+            Ok(IncomingMessage::Data(data, info)) => {
+                println!("Received {} bytes on stream {:?}", data.len(), info.stream)
+            }
+            Err(e) => println!("{:?}", e.kind()),
+        }
+    }
+}
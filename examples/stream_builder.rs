@@ -0,0 +1,27 @@
+extern crate sctp;
+use sctp::*;
+
+fn main() {
+    // Configure the socket before connecting: the number of streams is negotiated
+    // during the association setup, and can't be changed afterwards
+    let builder = SctpStreamBuilder::new()
+        .initmsg(64, 64, 0, 0)
+        .nodelay(true)
+        .send_buffer(65536)
+        .bind_local("127.0.0.1:0".parse().unwrap());
+    match builder.connect("127.0.0.1:3868") {
+        Err(e) => println!("{:?}", e.kind()),
+        Ok(peer) => {
+            let status = peer.status().unwrap();
+            println!(
+                "Negotiated {} outbound and {} inbound streams",
+                status.outstreams, status.instreams
+            );
+            // Write a message on stream 42, only available thanks to initmsg
+            peer.sendmsg("foo bar\n".as_bytes(), 42).unwrap();
+            let mut data = [0u8; 1024];
+            let (size, stream) = peer.recvmsg(&mut data).unwrap();
+            println!("Received {} bytes on stream {}", size, stream);
+        }
+    }
+}
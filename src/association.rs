@@ -0,0 +1,103 @@
+//! Handles on single associations of a one-to-many endpoint
+
+use std::io::Result;
+use std::net::SocketAddr;
+
+use sctp_sys::{sctp_assoc_t, sctp_sndrcvinfo};
+
+use crate::ffi;
+use crate::paddrparams::PeerAddrParams;
+use crate::status::{AssocStats, SctpStatus};
+use crate::SctpEndpoint;
+
+/// An association of a one-to-many `SctpEndpoint`.
+/// Obtained with `SctpEndpoint::association`, it bundles the association id together with
+/// the endpoint it belongs to, and exposes association-scoped operations
+pub struct Association<'a> {
+    endpoint: &'a SctpEndpoint,
+    id: sctp_assoc_t,
+}
+
+impl<'a> Association<'a> {
+    pub(crate) fn new(endpoint: &'a SctpEndpoint, id: sctp_assoc_t) -> Association<'a> {
+        Association { endpoint, id }
+    }
+
+    /// Get the association id
+    pub fn id(&self) -> sctp_assoc_t {
+        self.id
+    }
+
+    /// Get the endpoint this association belongs to
+    pub fn endpoint(&self) -> &'a SctpEndpoint {
+        self.endpoint
+    }
+
+    /// Get the live status of this association
+    pub fn status(&self) -> Result<SctpStatus> {
+        self.endpoint.0.status(self.id)
+    }
+
+    /// Get the statistics of this association
+    pub fn assoc_stats(&self) -> Result<AssocStats> {
+        Ok(AssocStats::from_raw(&self.endpoint.0.assoc_stats(self.id)?))
+    }
+
+    /// Return the list of socket addresses of the peer
+    pub fn peer_addrs(&self) -> Result<Vec<SocketAddr>> {
+        self.endpoint.0.peer_addrs(self.id)
+    }
+
+    /// Set the parameters of the path to the peer address `addr`, or the default
+    /// parameters of this association if `None`
+    pub fn set_peer_addr_params(
+        &self,
+        addr: Option<SocketAddr>,
+        params: PeerAddrParams,
+    ) -> Result<()> {
+        self.endpoint
+            .0
+            .set_peer_addr_params(self.id, addr.as_ref(), &params)
+    }
+
+    /// Get the parameters of the path to the peer address `addr`, or the default
+    /// parameters of this association if `None`
+    pub fn peer_addr_params(&self, addr: Option<SocketAddr>) -> Result<PeerAddrParams> {
+        self.endpoint.0.peer_addr_params(self.id, addr.as_ref())
+    }
+
+    /// Get the smallest path MTU among all the paths of this association
+    pub fn min_path_mtu(&self) -> Result<u32> {
+        self.endpoint.0.min_path_mtu(self.id)
+    }
+
+    /// Send bytes on the stream `stream` of this association.
+    /// On success, returns the quantity on bytes sent
+    pub fn send(&self, msg: &[u8], stream: u16) -> Result<usize> {
+        self.endpoint.0.send_info(msg, &self.sndinfo(stream, 0))
+    }
+
+    /// Gracefully shutdown this association. Other associations of the endpoint are not affected
+    pub fn shutdown(&self) -> Result<()> {
+        self.endpoint
+            .0
+            .send_info(&[], &self.sndinfo(0, ffi::SCTP_EOF))?;
+        Ok(())
+    }
+
+    /// Abort this association. Other associations of the endpoint are not affected
+    pub fn abort(&self) -> Result<()> {
+        self.endpoint
+            .0
+            .send_info(&[], &self.sndinfo(0, sctp_sys::SCTP_ABORT as u16))?;
+        Ok(())
+    }
+
+    fn sndinfo(&self, stream: u16, flags: u16) -> sctp_sndrcvinfo {
+        let mut info: sctp_sndrcvinfo = unsafe { std::mem::zeroed() };
+        info.sinfo_stream = stream;
+        info.sinfo_flags = flags;
+        info.sinfo_assoc_id = self.id;
+        info
+    }
+}
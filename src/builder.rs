@@ -0,0 +1,393 @@
+//! Builders used to configure sockets before they are bound or connected
+
+use std::io::Result;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
+
+use libc::{SOCK_STREAM, SOL_SOCKET, SO_RCVBUF, SO_RCVTIMEO, SO_REUSEADDR, SO_SNDBUF, SO_SNDTIMEO};
+use sctp_sys::{SOCK_SEQPACKET, SOL_SCTP};
+
+use crate::ffi;
+use crate::notifications::EventSubscription;
+use crate::sctpsock::{BindOp, RawSocketAddr, SctpSocket};
+use crate::{new_socket, new_socket_for, resolve_addrs, SctpEndpoint, SctpListener, SctpStream};
+
+/// Socket options collected by the builders, applied to a freshly created socket
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SocketConfig {
+    nodelay: Option<bool>,
+    send_buffer: Option<usize>,
+    recv_buffer: Option<usize>,
+    reuse_addr: Option<bool>,
+    initmsg: Option<(u16, u16, u16, u16)>,
+    max_init_attempts: Option<u16>,
+    stream_reset: Option<bool>,
+    events: Option<EventSubscription>,
+    autoclose: Option<u32>,
+    read_timeout: Option<Option<Duration>>,
+    write_timeout: Option<Option<Duration>>,
+}
+
+impl SocketConfig {
+    /// Capture the current values of the options of a one-to-one socket
+    fn capture(sock: &SctpSocket) -> Result<SocketConfig> {
+        let nodelay: libc::c_int = sock.sctp_opt_info(sctp_sys::SCTP_NODELAY, 0)?;
+        let send_buffer: libc::c_int = sock.getsockopt(SOL_SOCKET, SO_SNDBUF)?;
+        let recv_buffer: libc::c_int = sock.getsockopt(SOL_SOCKET, SO_RCVBUF)?;
+        let reuse_addr: libc::c_int = sock.getsockopt(SOL_SOCKET, SO_REUSEADDR)?;
+        let initmsg = sock.initmsg()?;
+        Ok(SocketConfig {
+            nodelay: Some(nodelay != 0),
+            // Linux reports the doubled size, which it doubles again when set
+            send_buffer: Some(send_buffer as usize / 2),
+            recv_buffer: Some(recv_buffer as usize / 2),
+            reuse_addr: Some(reuse_addr != 0),
+            initmsg: Some((
+                initmsg.sinit_num_ostreams,
+                initmsg.sinit_max_instreams,
+                initmsg.sinit_max_attempts,
+                initmsg.sinit_max_init_timeo,
+            )),
+            max_init_attempts: None,
+            stream_reset: None,
+            events: Some(sock.events()?),
+            autoclose: None,
+            read_timeout: None,
+            write_timeout: None,
+        })
+    }
+
+    pub(crate) fn apply(&self, sock: &SctpSocket) -> Result<()> {
+        if let Some(nodelay) = self.nodelay {
+            let val: libc::c_int = if nodelay { 1 } else { 0 };
+            sock.setsockopt(SOL_SCTP, sctp_sys::SCTP_NODELAY, &val)?;
+        }
+        if let Some(size) = self.send_buffer {
+            sock.setsockopt(SOL_SOCKET, SO_SNDBUF, &(size as libc::c_int))?;
+        }
+        if let Some(size) = self.recv_buffer {
+            sock.setsockopt(SOL_SOCKET, SO_RCVBUF, &(size as libc::c_int))?;
+        }
+        if let Some(reuse) = self.reuse_addr {
+            let val: libc::c_int = if reuse { 1 } else { 0 };
+            sock.setsockopt(SOL_SOCKET, SO_REUSEADDR, &val)?;
+        }
+        if let Some((out_streams, in_streams, max_attempts, max_init_timeo)) = self.initmsg {
+            sock.set_initmsg(out_streams, in_streams, max_attempts, max_init_timeo)?;
+        }
+        if let Some(attempts) = self.max_init_attempts {
+            sock.set_max_init_attempts(attempts)?;
+        }
+        if let Some(on) = self.stream_reset {
+            sock.set_reconfig_supported(on)?;
+            let flags = if on {
+                ffi::SCTP_ENABLE_RESET_STREAM_REQ | ffi::SCTP_ENABLE_CHANGE_ASSOC_REQ
+            } else {
+                0
+            };
+            sock.set_enable_stream_reset(0, flags)?;
+        }
+        if let Some(ref events) = self.events {
+            sock.subscribe_events(events)?;
+        }
+        if let Some(seconds) = self.autoclose {
+            sock.set_autoclose(seconds)?;
+        }
+        if let Some(timeout) = self.read_timeout {
+            sock.set_timeout(SO_RCVTIMEO, timeout)?;
+        }
+        if let Some(timeout) = self.write_timeout {
+            sock.set_timeout(SO_SNDTIMEO, timeout)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for `SctpEndpoint`, used to set socket options which must be configured
+/// before the endpoint starts serving associations
+#[derive(Debug, Clone, Default)]
+pub struct SctpEndpointBuilder {
+    config: SocketConfig,
+}
+
+impl SctpEndpointBuilder {
+    /// Create a builder with the system default options
+    pub fn new() -> SctpEndpointBuilder {
+        SctpEndpointBuilder::default()
+    }
+
+    /// Set or unset SCTP_NODELAY option
+    pub fn nodelay(mut self, nodelay: bool) -> SctpEndpointBuilder {
+        self.config.nodelay = Some(nodelay);
+        self
+    }
+
+    /// Set the send buffer size. Linux systems will double the provided size
+    pub fn send_buffer(mut self, size: usize) -> SctpEndpointBuilder {
+        self.config.send_buffer = Some(size);
+        self
+    }
+
+    /// Set the receive buffer size. Linux systems will double the provided size
+    pub fn recv_buffer(mut self, size: usize) -> SctpEndpointBuilder {
+        self.config.recv_buffer = Some(size);
+        self
+    }
+
+    /// Set or unset SO_REUSEADDR option
+    pub fn reuse_addr(mut self, reuse: bool) -> SctpEndpointBuilder {
+        self.config.reuse_addr = Some(reuse);
+        self
+    }
+
+    /// Set the parameters used when initiating new associations (`SCTP_INITMSG`).
+    /// Zero values keep the kernel defaults
+    pub fn initmsg(
+        mut self,
+        out_streams: u16,
+        in_streams: u16,
+        max_attempts: u16,
+        max_init_timeo: u16,
+    ) -> SctpEndpointBuilder {
+        self.config.initmsg = Some((out_streams, in_streams, max_attempts, max_init_timeo));
+        self
+    }
+
+    /// Set how many times INIT is retransmitted before giving up on a new association,
+    /// keeping the other init parameters unchanged
+    pub fn max_init_attempts(mut self, attempts: u16) -> SctpEndpointBuilder {
+        self.config.max_init_attempts = Some(attempts);
+        self
+    }
+
+    /// Subscribe to SCTP events
+    pub fn subscribe_events(mut self, events: EventSubscription) -> SctpEndpointBuilder {
+        self.config.events = Some(events);
+        self
+    }
+
+    /// Automatically close associations idle for more than `seconds`. 0 disables autoclose
+    pub fn autoclose(mut self, seconds: u32) -> SctpEndpointBuilder {
+        self.config.autoclose = Some(seconds);
+        self
+    }
+
+    /// Create the endpoint and bind it to a single address
+    pub fn bind<A: ToSocketAddrs>(&self, address: A) -> Result<SctpEndpoint> {
+        let raw_addr = SocketAddr::from_addr(&address)?;
+        let sock = new_socket(raw_addr.family(), SOCK_SEQPACKET)?;
+        self.config.apply(&sock)?;
+        sock.bind(raw_addr)?;
+        sock.listen(-1)?;
+        Ok(SctpEndpoint(sock))
+    }
+
+    /// Create the endpoint and bind it to multiple addresses. Requires at least one address
+    pub fn bindx<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<SctpEndpoint> {
+        let (family, mut addrs) = resolve_addrs(addresses)?;
+        let sock = new_socket_for(family, &mut addrs, SOCK_SEQPACKET)?;
+        self.config.apply(&sock)?;
+        sock.bindx(&addrs, BindOp::AddAddr)?;
+        sock.listen(-1)?;
+        Ok(SctpEndpoint(sock))
+    }
+}
+
+/// Builder for `SctpStream`, used to set socket options which must be configured before
+/// the association is set up, such as the number of streams requested with `initmsg`
+#[derive(Debug, Clone, Default)]
+pub struct SctpStreamBuilder {
+    config: SocketConfig,
+    local_addrs: Vec<SocketAddr>,
+}
+
+impl SctpStreamBuilder {
+    /// Create a builder with the system default options
+    pub fn new() -> SctpStreamBuilder {
+        SctpStreamBuilder::default()
+    }
+
+    /// Set or unset SCTP_NODELAY option
+    pub fn nodelay(mut self, nodelay: bool) -> SctpStreamBuilder {
+        self.config.nodelay = Some(nodelay);
+        self
+    }
+
+    /// Set the send buffer size. Linux systems will double the provided size
+    pub fn send_buffer(mut self, size: usize) -> SctpStreamBuilder {
+        self.config.send_buffer = Some(size);
+        self
+    }
+
+    /// Set the receive buffer size. Linux systems will double the provided size
+    pub fn recv_buffer(mut self, size: usize) -> SctpStreamBuilder {
+        self.config.recv_buffer = Some(size);
+        self
+    }
+
+    /// Set the parameters of the association (`SCTP_INITMSG`): number of requested outbound
+    /// streams, maximum inbound streams, INIT retransmissions and INIT timeout.
+    /// Zero values keep the kernel defaults.
+    /// The stream counts are negotiated during association setup, so they are applied to the
+    /// socket before it connects. The numbers actually negotiated with the peer can be read
+    /// afterwards with `SctpStream::status`
+    pub fn initmsg(
+        mut self,
+        out_streams: u16,
+        in_streams: u16,
+        max_attempts: u16,
+        max_init_timeo: u16,
+    ) -> SctpStreamBuilder {
+        self.config.initmsg = Some((out_streams, in_streams, max_attempts, max_init_timeo));
+        self
+    }
+
+    /// Set how many times INIT is retransmitted before giving up, so that connecting to an
+    /// unreachable peer fails faster
+    pub fn max_init_attempts(mut self, attempts: u16) -> SctpStreamBuilder {
+        self.config.max_init_attempts = Some(attempts);
+        self
+    }
+
+    /// Negotiate the stream reconfiguration extension (RFC 6525), and enable stream reset
+    /// and stream addition requests, needed by `SctpStream::reset_streams` and `add_streams`
+    pub fn enable_stream_reset(mut self, on: bool) -> SctpStreamBuilder {
+        self.config.stream_reset = Some(on);
+        self
+    }
+
+    /// Subscribe to SCTP events
+    pub fn subscribe_events(mut self, events: EventSubscription) -> SctpStreamBuilder {
+        self.config.events = Some(events);
+        self
+    }
+
+    /// Bind the stream to the local address `address` before connecting. Call it several
+    /// times to advertise several local addresses to the peer as paths of the association.
+    /// Addresses with port 0 share an ephemeral port, non-zero ports must all be the same
+    pub fn bind_local(mut self, address: SocketAddr) -> SctpStreamBuilder {
+        self.local_addrs.push(address);
+        self
+    }
+
+    /// Create the socket for remote addresses of `family`, configure it and bind it to the
+    /// local addresses
+    fn open(&self, family: libc::c_int, addrs: &mut Vec<SocketAddr>) -> Result<SctpSocket> {
+        let family = if self.local_addrs.iter().any(|a| a.is_ipv6()) {
+            libc::AF_INET6
+        } else {
+            family
+        };
+        let sock = new_socket_for(family, addrs, SOCK_STREAM)?;
+        self.config.apply(&sock)?;
+        if !self.local_addrs.is_empty() {
+            sock.bindx(&self.local_addrs, BindOp::AddAddr)?;
+        }
+        Ok(sock)
+    }
+
+    /// Create the stream and connect it to a single address
+    pub fn connect<A: ToSocketAddrs>(&self, address: A) -> Result<SctpStream> {
+        let raw_addr = SocketAddr::from_addr(&address)?;
+        let sock = if self.local_addrs.is_empty() {
+            let sock = new_socket(raw_addr.family(), SOCK_STREAM)?;
+            self.config.apply(&sock)?;
+            sock
+        } else {
+            self.open(raw_addr.family(), &mut vec![raw_addr])?
+        };
+        sock.connect(raw_addr)?;
+        Ok(SctpStream::from_socket(sock))
+    }
+
+    /// Create the stream and connect it to a remote endpoint having multiple addresses.
+    /// On hosts without IPv6 support, IPv6 addresses are ignored
+    pub fn connectx<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<SctpStream> {
+        let (family, mut addrs) = resolve_addrs(addresses)?;
+        let sock = self.open(family, &mut addrs)?;
+        sock.connectx(&addrs)?;
+        Ok(SctpStream::from_socket(sock))
+    }
+}
+
+/// Options applied to each stream accepted by a `SctpListener`, registered with
+/// `SctpListener::set_accept_template`. Options left unset keep the value inherited
+/// from the listener
+#[derive(Debug, Clone, Default)]
+pub struct SctpOptions {
+    pub(crate) config: SocketConfig,
+}
+
+impl SctpOptions {
+    /// Create an empty set of options
+    pub fn new() -> SctpOptions {
+        SctpOptions::default()
+    }
+
+    /// Set or unset SCTP_NODELAY option
+    pub fn nodelay(mut self, nodelay: bool) -> SctpOptions {
+        self.config.nodelay = Some(nodelay);
+        self
+    }
+
+    /// Set the send buffer size. Linux systems will double the provided size
+    pub fn send_buffer(mut self, size: usize) -> SctpOptions {
+        self.config.send_buffer = Some(size);
+        self
+    }
+
+    /// Set the receive buffer size. Linux systems will double the provided size
+    pub fn recv_buffer(mut self, size: usize) -> SctpOptions {
+        self.config.recv_buffer = Some(size);
+        self
+    }
+
+    /// Set the read timeout (`SO_RCVTIMEO`). `None` clears the timeout
+    pub fn read_timeout(mut self, timeout: Option<Duration>) -> SctpOptions {
+        self.config.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the write timeout (`SO_SNDTIMEO`). `None` clears the timeout
+    pub fn write_timeout(mut self, timeout: Option<Duration>) -> SctpOptions {
+        self.config.write_timeout = Some(timeout);
+        self
+    }
+}
+
+/// Options of a `SctpListener`, captured with `SctpListener::config`, used to create
+/// other listeners configured the same way (buffer sizes, nodelay, reuse address,
+/// association init parameters and event subscriptions)
+#[derive(Debug, Clone)]
+pub struct ListenerConfig {
+    config: SocketConfig,
+}
+
+impl ListenerConfig {
+    pub(crate) fn capture(listener: &SctpListener) -> Result<ListenerConfig> {
+        Ok(ListenerConfig {
+            config: SocketConfig::capture(&listener.0)?,
+        })
+    }
+
+    /// Create a listener with these options, bound to a single address
+    pub fn bind<A: ToSocketAddrs>(&self, address: A) -> Result<SctpListener> {
+        let raw_addr = SocketAddr::from_addr(&address)?;
+        let sock = new_socket(raw_addr.family(), SOCK_STREAM)?;
+        self.config.apply(&sock)?;
+        sock.bind(raw_addr)?;
+        sock.listen(-1)?;
+        Ok(SctpListener::from_socket(sock))
+    }
+
+    /// Create a listener with these options, bound to multiple addresses.
+    /// Requires at least one address
+    pub fn bindx<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<SctpListener> {
+        let (family, mut addrs) = resolve_addrs(addresses)?;
+        let sock = new_socket_for(family, &mut addrs, SOCK_STREAM)?;
+        self.config.apply(&sock)?;
+        sock.bindx(&addrs, BindOp::AddAddr)?;
+        sock.listen(-1)?;
+        Ok(SctpListener::from_socket(sock))
+    }
+}
@@ -0,0 +1,91 @@
+//! Cancellation of blocking operations.
+//!
+//! A `ShutdownToken` wraps an `eventfd` which can be triggered from any thread.
+//! Blocking operations taking a token wait on both the socket and the token, and
+//! return an `ErrorKind::Interrupted` error carrying a `Cancelled` marker as soon as
+//! the token is triggered.
+
+use std::error;
+use std::fmt;
+use std::io::{Error, ErrorKind, Result};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::Arc;
+
+use crate::syscall;
+
+/// Marker error returned by blocking operations interrupted by a `ShutdownToken`
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Operation cancelled by shutdown token")
+    }
+}
+
+impl error::Error for Cancelled {}
+
+impl Cancelled {
+    /// Check if `err` was produced by a triggered `ShutdownToken`
+    pub fn is(err: &Error) -> bool {
+        err.kind() == ErrorKind::Interrupted && err.get_ref().is_some_and(|e| e.is::<Cancelled>())
+    }
+
+    pub(crate) fn error() -> Error {
+        Error::new(ErrorKind::Interrupted, Cancelled)
+    }
+}
+
+struct EventFd(RawFd);
+
+impl Drop for EventFd {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.0) };
+    }
+}
+
+/// Token used to unblock threads waiting in `accept` or `recvmsg`.
+/// Clones share the same underlying event, so triggering one of them wakes up
+/// every operation waiting on any clone. Once triggered, a token stays triggered.
+#[derive(Clone)]
+pub struct ShutdownToken(Arc<EventFd>);
+
+impl ShutdownToken {
+    /// Create a new, untriggered token
+    pub fn new() -> Result<ShutdownToken> {
+        let fd = syscall!(eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK))?;
+        Ok(ShutdownToken(Arc::new(EventFd(fd))))
+    }
+
+    /// Trigger the token, waking up all operations blocked on it
+    pub fn wake(&self) -> Result<()> {
+        let val: u64 = 1;
+        match syscall!(write(
+            self.0 .0,
+            &val as *const u64 as *const libc::c_void,
+            std::mem::size_of::<u64>()
+        )) {
+            // Counter saturated: the token is already triggered
+            Err(ref err) if err.kind() == ErrorKind::WouldBlock => Ok(()),
+            Err(err) => Err(err),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// Check if the token has been triggered
+    pub fn is_woken(&self) -> Result<bool> {
+        let mut pfd = libc::pollfd {
+            fd: self.0 .0,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let n = syscall!(poll(&mut pfd, 1, 0))?;
+        Ok(n > 0)
+    }
+}
+
+impl AsRawFd for ShutdownToken {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0 .0
+    }
+}
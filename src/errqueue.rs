@@ -0,0 +1,85 @@
+//! Extended socket errors, as delivered by the socket error queue when
+//! `IP_RECVERR` / `IPV6_RECVERR` are enabled (Linux only)
+
+use std::net::SocketAddr;
+use std::ops::RangeInclusive;
+
+use crate::ffi;
+use crate::mio_unix::to_socket_addr;
+
+/// An extended error read from the socket error queue
+#[derive(Debug, Clone)]
+pub struct SctpExtError {
+    /// Error number carried by the error
+    pub errno: u32,
+    /// Where the error comes from (one of the `libc::SO_EE_ORIGIN_*` values)
+    pub origin: u8,
+    /// ICMP type, when the error originates from an ICMP message
+    pub icmp_type: u8,
+    /// ICMP code, when the error originates from an ICMP message
+    pub icmp_code: u8,
+    /// Additional information (e.g. the discovered MTU for "fragmentation needed" errors)
+    pub info: u32,
+    /// Additional data (e.g. the end of the range of completed zero-copy sends)
+    pub data: u32,
+    /// Address of the node which reported the error, if any
+    pub offender: Option<SocketAddr>,
+}
+
+impl SctpExtError {
+    /// Check if this error was derived from an ICMP or ICMPv6 message
+    pub fn is_icmp(&self) -> bool {
+        self.origin == libc::SO_EE_ORIGIN_ICMP || self.origin == libc::SO_EE_ORIGIN_ICMP6
+    }
+
+    /// If this is a zero-copy completion notification, get the range of completed
+    /// zero-copy sends. Sends are numbered from 0, in the order they were made
+    pub fn zerocopy_completed(&self) -> Option<RangeInclusive<u32>> {
+        if self.origin == ffi::SO_EE_ORIGIN_ZEROCOPY {
+            Some(self.info..=self.data)
+        } else {
+            None
+        }
+    }
+
+    /// Check if the kernel fell back to copying the data of the completed zero-copy sends
+    pub fn zerocopy_copied(&self) -> bool {
+        self.origin == ffi::SO_EE_ORIGIN_ZEROCOPY
+            && self.icmp_code & ffi::SO_EE_CODE_ZEROCOPY_COPIED != 0
+    }
+}
+
+/// Look for an extended error in the control messages of `msg`
+///
+/// # Safety
+///
+/// `msg` must have been filled by a successful `recvmsg` call with `MSG_ERRQUEUE`
+pub(crate) unsafe fn parse(msg: &libc::msghdr) -> Option<SctpExtError> {
+    let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+    while !cmsg.is_null() {
+        let level = (*cmsg).cmsg_level;
+        let ty = (*cmsg).cmsg_type;
+        if (level == libc::SOL_IP && ty == libc::IP_RECVERR)
+            || (level == libc::SOL_IPV6 && ty == libc::IPV6_RECVERR)
+        {
+            let ee = libc::CMSG_DATA(cmsg) as *const libc::sock_extended_err;
+            let err = std::ptr::read_unaligned(ee);
+            let offender = libc::SO_EE_OFFENDER(ee) as *const libc::sockaddr_storage;
+            let offender = match (*(offender as *const libc::sockaddr)).sa_family as libc::c_int {
+                libc::AF_INET | libc::AF_INET6 => to_socket_addr(offender).ok(),
+                _ => None,
+            };
+            return Some(SctpExtError {
+                errno: err.ee_errno,
+                origin: err.ee_origin,
+                icmp_type: err.ee_type,
+                icmp_code: err.ee_code,
+                info: err.ee_info,
+                data: err.ee_data,
+                offender,
+            });
+        }
+        cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+    }
+    None
+}
@@ -0,0 +1,435 @@
+//! Raw SCTP structures and constants from `linux/sctp.h` which are not provided by `sctp-sys`
+
+#![allow(non_camel_case_types, dead_code)]
+
+use sctp_sys::sctp_assoc_t;
+
+// Socket options not defined by sctp-sys
+pub const SCTP_PR_SUPPORTED: libc::c_int = 113;
+pub const SCTP_DEFAULT_PRINFO: libc::c_int = 114;
+pub const SCTP_PR_ASSOC_STATUS: libc::c_int = 115;
+pub const SCTP_PR_STREAM_STATUS: libc::c_int = 116;
+pub const SCTP_RECONFIG_SUPPORTED: libc::c_int = 117;
+pub const SCTP_ENABLE_STREAM_RESET: libc::c_int = 118;
+pub const SCTP_RESET_STREAMS: libc::c_int = 119;
+pub const SCTP_RESET_ASSOC: libc::c_int = 120;
+pub const SCTP_ADD_STREAMS: libc::c_int = 121;
+pub const SCTP_SOCKOPT_PEELOFF_FLAGS: libc::c_int = 122;
+pub const SCTP_STREAM_SCHEDULER: libc::c_int = 123;
+pub const SCTP_STREAM_SCHEDULER_VALUE: libc::c_int = 124;
+pub const SCTP_INTERLEAVING_SUPPORTED: libc::c_int = 125;
+pub const SCTP_SENDMSG_CONNECT: libc::c_int = 126;
+pub const SCTP_EVENT: libc::c_int = 127;
+pub const SCTP_ASCONF_SUPPORTED: libc::c_int = 128;
+pub const SCTP_AUTH_SUPPORTED: libc::c_int = 129;
+pub const SCTP_ECN_SUPPORTED: libc::c_int = 130;
+
+// sctp_sstat_state
+pub const SCTP_EMPTY: i32 = 0;
+pub const SCTP_CLOSED: i32 = 1;
+pub const SCTP_COOKIE_WAIT: i32 = 2;
+pub const SCTP_COOKIE_ECHOED: i32 = 3;
+pub const SCTP_ESTABLISHED: i32 = 4;
+pub const SCTP_SHUTDOWN_PENDING: i32 = 5;
+pub const SCTP_SHUTDOWN_SENT: i32 = 6;
+pub const SCTP_SHUTDOWN_RECEIVED: i32 = 7;
+pub const SCTP_SHUTDOWN_ACK_SENT: i32 = 8;
+
+/// Graceful shutdown send flag (`SCTP_EOF`, an alias of `MSG_FIN`)
+pub const SCTP_EOF: u16 = libc::MSG_FIN as u16;
+
+#[repr(C, packed(4))]
+#[derive(Copy, Clone)]
+pub struct sctp_paddrinfo {
+    pub spinfo_assoc_id: sctp_assoc_t,
+    pub spinfo_address: libc::sockaddr_storage,
+    pub spinfo_state: i32,
+    pub spinfo_cwnd: u32,
+    pub spinfo_srtt: u32,
+    pub spinfo_rto: u32,
+    pub spinfo_mtu: u32,
+}
+
+#[repr(C, packed(4))]
+#[derive(Copy, Clone)]
+pub struct sctp_status {
+    pub sstat_assoc_id: sctp_assoc_t,
+    pub sstat_state: i32,
+    pub sstat_rwnd: u32,
+    pub sstat_unackdata: u16,
+    pub sstat_penddata: u16,
+    pub sstat_instrms: u16,
+    pub sstat_outstrms: u16,
+    pub sstat_fragmentation_point: u32,
+    pub sstat_primary: sctp_paddrinfo,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+pub struct sctp_initmsg {
+    pub sinit_num_ostreams: u16,
+    pub sinit_max_instreams: u16,
+    pub sinit_max_attempts: u16,
+    pub sinit_max_init_timeo: u16,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+pub struct sctp_event_subscribe {
+    pub sctp_data_io_event: u8,
+    pub sctp_association_event: u8,
+    pub sctp_address_event: u8,
+    pub sctp_send_failure_event: u8,
+    pub sctp_peer_error_event: u8,
+    pub sctp_shutdown_event: u8,
+    pub sctp_partial_delivery_event: u8,
+    pub sctp_adaptation_layer_event: u8,
+    pub sctp_authentication_event: u8,
+    pub sctp_sender_dry_event: u8,
+    pub sctp_stream_reset_event: u8,
+}
+
+/// Flag set on received buffers holding a notification instead of user data
+pub const MSG_NOTIFICATION: libc::c_int = 0x8000;
+
+// sctp_cmsg_type
+pub const SCTP_INIT: libc::c_int = 0;
+pub const SCTP_SNDRCV: libc::c_int = 1;
+pub const SCTP_SNDINFO: libc::c_int = 2;
+pub const SCTP_RCVINFO: libc::c_int = 3;
+pub const SCTP_NXTINFO: libc::c_int = 4;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_sndinfo {
+    pub snd_sid: u16,
+    pub snd_flags: u16,
+    pub snd_ppid: u32,
+    pub snd_context: u32,
+    pub snd_assoc_id: sctp_assoc_t,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_rcvinfo {
+    pub rcv_sid: u16,
+    pub rcv_ssn: u16,
+    pub rcv_flags: u16,
+    pub rcv_ppid: u32,
+    pub rcv_tsn: u32,
+    pub rcv_cumtsn: u32,
+    pub rcv_context: u32,
+    pub rcv_assoc_id: sctp_assoc_t,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_nxtinfo {
+    pub nxt_sid: u16,
+    pub nxt_flags: u16,
+    pub nxt_ppid: u32,
+    pub nxt_length: u32,
+    pub nxt_assoc_id: sctp_assoc_t,
+}
+
+// spp_flags
+pub const SPP_HB_ENABLE: u32 = 1 << 0;
+pub const SPP_HB_DISABLE: u32 = 1 << 1;
+pub const SPP_HB_DEMAND: u32 = 1 << 2;
+pub const SPP_PMTUD_ENABLE: u32 = 1 << 3;
+pub const SPP_PMTUD_DISABLE: u32 = 1 << 4;
+pub const SPP_SACKDELAY_ENABLE: u32 = 1 << 5;
+pub const SPP_SACKDELAY_DISABLE: u32 = 1 << 6;
+pub const SPP_HB_TIME_IS_ZERO: u32 = 1 << 7;
+
+/// Legacy layout of `sctp_paddrparams`, without the flowlabel and dscp fields,
+/// which is accepted by every kernel version. The C struct is fully packed,
+/// then padded to a multiple of 4 bytes
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+pub struct sctp_paddrparams {
+    pub spp_assoc_id: sctp_assoc_t,
+    pub spp_address: libc::sockaddr_storage,
+    pub spp_hbinterval: u32,
+    pub spp_pathmaxrxt: u16,
+    pub spp_pathmtu: u32,
+    pub spp_sackdelay: u32,
+    pub spp_flags: u32,
+    pub _pad: [u8; 2],
+}
+
+#[repr(C, packed(4))]
+#[derive(Copy, Clone)]
+pub struct sctp_prim {
+    pub ssp_assoc_id: sctp_assoc_t,
+    pub ssp_addr: libc::sockaddr_storage,
+}
+
+// sctp_sn_type
+pub const SCTP_SN_TYPE_BASE: u16 = 1 << 15;
+pub const SCTP_ASSOC_CHANGE: u16 = SCTP_SN_TYPE_BASE + 1;
+pub const SCTP_PEER_ADDR_CHANGE: u16 = SCTP_SN_TYPE_BASE + 2;
+pub const SCTP_SEND_FAILED: u16 = SCTP_SN_TYPE_BASE + 3;
+pub const SCTP_REMOTE_ERROR: u16 = SCTP_SN_TYPE_BASE + 4;
+pub const SCTP_SHUTDOWN_EVENT: u16 = SCTP_SN_TYPE_BASE + 5;
+pub const SCTP_PARTIAL_DELIVERY_EVENT: u16 = SCTP_SN_TYPE_BASE + 6;
+pub const SCTP_ADAPTATION_INDICATION: u16 = SCTP_SN_TYPE_BASE + 7;
+pub const SCTP_AUTHENTICATION_EVENT: u16 = SCTP_SN_TYPE_BASE + 8;
+pub const SCTP_SENDER_DRY_EVENT: u16 = SCTP_SN_TYPE_BASE + 9;
+pub const SCTP_STREAM_RESET_EVENT: u16 = SCTP_SN_TYPE_BASE + 10;
+pub const SCTP_ASSOC_RESET_EVENT: u16 = SCTP_SN_TYPE_BASE + 11;
+pub const SCTP_STREAM_CHANGE_EVENT: u16 = SCTP_SN_TYPE_BASE + 12;
+pub const SCTP_SEND_FAILED_EVENT: u16 = SCTP_SN_TYPE_BASE + 13;
+
+// sctp_pdapi_indication
+pub const SCTP_PARTIAL_DELIVERY_ABORTED: u32 = 0;
+
+// sctp_sac_state
+pub const SCTP_COMM_UP: u16 = 0;
+pub const SCTP_COMM_LOST: u16 = 1;
+pub const SCTP_RESTART: u16 = 2;
+pub const SCTP_SHUTDOWN_COMP: u16 = 3;
+pub const SCTP_CANT_STR_ASSOC: u16 = 4;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_assoc_change {
+    pub sac_type: u16,
+    pub sac_flags: u16,
+    pub sac_length: u32,
+    pub sac_state: u16,
+    pub sac_error: u16,
+    pub sac_outbound_streams: u16,
+    pub sac_inbound_streams: u16,
+    pub sac_assoc_id: sctp_assoc_t,
+}
+
+// sctp_spc_state
+pub const SCTP_ADDR_AVAILABLE: i32 = 0;
+pub const SCTP_ADDR_UNREACHABLE: i32 = 1;
+pub const SCTP_ADDR_REMOVED: i32 = 2;
+pub const SCTP_ADDR_ADDED: i32 = 3;
+pub const SCTP_ADDR_MADE_PRIM: i32 = 4;
+pub const SCTP_ADDR_CONFIRMED: i32 = 5;
+pub const SCTP_ADDR_POTENTIALLY_FAILED: i32 = 6;
+
+#[repr(C, packed(4))]
+#[derive(Copy, Clone)]
+pub struct sctp_paddr_change {
+    pub spc_type: u16,
+    pub spc_flags: u16,
+    pub spc_length: u32,
+    pub spc_aaddr: libc::sockaddr_storage,
+    pub spc_state: i32,
+    pub spc_error: i32,
+    pub spc_assoc_id: sctp_assoc_t,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_notification_header {
+    pub sn_type: u16,
+    pub sn_flags: u16,
+    pub sn_length: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_pdapi_event {
+    pub pdapi_type: u16,
+    pub pdapi_flags: u16,
+    pub pdapi_length: u32,
+    pub pdapi_indication: u32,
+    pub pdapi_assoc_id: sctp_assoc_t,
+    pub pdapi_stream: u32,
+    pub pdapi_seq: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_remote_error {
+    pub sre_type: u16,
+    pub sre_flags: u16,
+    pub sre_length: u32,
+    pub sre_error: u16,
+    pub sre_assoc_id: sctp_assoc_t,
+}
+
+// ssf_flags
+pub const SCTP_DATA_UNSENT: u16 = 0;
+pub const SCTP_DATA_SENT: u16 = 1;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_send_failed {
+    pub ssf_type: u16,
+    pub ssf_flags: u16,
+    pub ssf_length: u32,
+    pub ssf_error: u32,
+    pub ssf_info: sctp_sys::sctp_sndrcvinfo,
+    pub ssf_assoc_id: sctp_assoc_t,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_shutdown_event {
+    pub sse_type: u16,
+    pub sse_flags: u16,
+    pub sse_length: u32,
+    pub sse_assoc_id: sctp_assoc_t,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_adaptation_event {
+    pub sai_type: u16,
+    pub sai_flags: u16,
+    pub sai_length: u32,
+    pub sai_adaptation_ind: u32,
+    pub sai_assoc_id: sctp_assoc_t,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_sender_dry_event {
+    pub sender_dry_type: u16,
+    pub sender_dry_flags: u16,
+    pub sender_dry_length: u32,
+    pub sender_dry_assoc_id: sctp_assoc_t,
+}
+
+// strreset_flags
+pub const SCTP_STREAM_RESET_INCOMING_SSN: u16 = 0x0001;
+pub const SCTP_STREAM_RESET_OUTGOING_SSN: u16 = 0x0002;
+pub const SCTP_STREAM_RESET_DENIED: u16 = 0x0004;
+pub const SCTP_STREAM_RESET_FAILED: u16 = 0x0008;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_stream_reset_event {
+    pub strreset_type: u16,
+    pub strreset_flags: u16,
+    pub strreset_length: u32,
+    pub strreset_assoc_id: sctp_assoc_t,
+}
+
+// PR-SCTP policies
+pub const SCTP_PR_SCTP_NONE: u16 = 0x0000;
+pub const SCTP_PR_SCTP_TTL: u16 = 0x0010;
+pub const SCTP_PR_SCTP_RTX: u16 = 0x0020;
+pub const SCTP_PR_SCTP_PRIO: u16 = 0x0030;
+pub const SCTP_PR_SCTP_MASK: u16 = 0x0030;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_default_prinfo {
+    pub pr_assoc_id: sctp_assoc_t,
+    pub pr_value: u32,
+    pub pr_policy: u16,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_assocparams {
+    pub sasoc_assoc_id: sctp_assoc_t,
+    pub sasoc_asocmaxrxt: u16,
+    pub sasoc_number_peer_destinations: u16,
+    pub sasoc_peer_rwnd: u32,
+    pub sasoc_local_rwnd: u32,
+    pub sasoc_cookie_life: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_assoc_stats {
+    pub sas_assoc_id: sctp_assoc_t,
+    pub sas_obs_rto_ipaddr: libc::sockaddr_storage,
+    pub sas_maxrto: u64,
+    pub sas_isacks: u64,
+    pub sas_osacks: u64,
+    pub sas_opackets: u64,
+    pub sas_ipackets: u64,
+    pub sas_rtxchunks: u64,
+    pub sas_outofseqtsns: u64,
+    pub sas_idupchunks: u64,
+    pub sas_gapcnt: u64,
+    pub sas_ouodchunks: u64,
+    pub sas_iuodchunks: u64,
+    pub sas_oodchunks: u64,
+    pub sas_iodchunks: u64,
+    pub sas_octrlchunks: u64,
+    pub sas_ictrlchunks: u64,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_assoc_value {
+    pub assoc_id: sctp_assoc_t,
+    pub assoc_value: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_rtoinfo {
+    pub srto_assoc_id: sctp_assoc_t,
+    pub srto_initial: u32,
+    pub srto_max: u32,
+    pub srto_min: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_sack_info {
+    pub sack_assoc_id: sctp_assoc_t,
+    pub sack_delay: u32,
+    pub sack_freq: u32,
+}
+
+/// Largest SACK delay accepted by `SCTP_DELAYED_SACK`, in milliseconds
+pub const SCTP_MAX_SACK_DELAY: u32 = 500;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_stream_value {
+    pub assoc_id: sctp_assoc_t,
+    pub stream_id: u16,
+    pub stream_value: u16,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sctp_add_streams {
+    pub sas_assoc_id: sctp_assoc_t,
+    pub sas_instrms: u16,
+    pub sas_outstrms: u16,
+}
+
+// SCTP_ENABLE_STREAM_RESET flags
+pub const SCTP_ENABLE_RESET_STREAM_REQ: u32 = 0x01;
+pub const SCTP_ENABLE_RESET_ASSOC_REQ: u32 = 0x02;
+pub const SCTP_ENABLE_CHANGE_ASSOC_REQ: u32 = 0x04;
+
+// sctp_reset_streams flags
+pub const SCTP_STREAM_RESET_INCOMING: u16 = 0x01;
+pub const SCTP_STREAM_RESET_OUTGOING: u16 = 0x02;
+
+// sctp_sched_type
+pub const SCTP_SS_FCFS: u32 = 0;
+pub const SCTP_SS_PRIO: u32 = 1;
+pub const SCTP_SS_RR: u32 = 2;
+
+// Pluggable congestion control, as defined by FreeBSD. Linux doesn't implement it
+pub const SCTP_PLUGGABLE_CC: libc::c_int = 0x1202;
+pub const SCTP_CC_RFC2581: u32 = 0;
+pub const SCTP_CC_HSTCP: u32 = 1;
+pub const SCTP_CC_HTCP: u32 = 2;
+pub const SCTP_CC_RTCC: u32 = 3;
+
+/// Names of the congestion control algorithms, indexed by their `SCTP_CC_*` value
+pub const SCTP_CC_NAMES: [&str; 4] = ["rfc2581", "hstcp", "htcp", "rtcc"];
+
+// Zero-copy sends
+/// Kernel internal "operation not supported" error, leaked to user space by older kernels
+pub const ENOTSUPP: libc::c_int = 524;
+pub const SO_EE_ORIGIN_ZEROCOPY: u8 = 5;
+pub const SO_EE_CODE_ZEROCOPY_COPIED: u8 = 1;
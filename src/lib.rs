@@ -1,8 +1,8 @@
 //! This crate provides high level SCTP networking.
 //! Currently it only supports basic SCTP features like multi-homing
 //! in one-to-one and one-to-many associations.
-//! SCTP notifications and working directly on associations is not supported yet
-//! but is in the TODO list.
+//! Associations of one-to-many endpoints can be worked on through `Association` handles.
+//! SCTP notifications can be subscribed to with `EventSubscription`, and decoded with `SctpNotification`.
 
 extern crate libc;
 extern crate sctp_sys;
@@ -11,18 +11,114 @@ extern crate ws2_32;
 
 mod sctpsock;
 use sctp_sys::{SOCK_SEQPACKET, SOL_SCTP};
+pub use sctpsock::{BatchMessage, RecvInfo};
 use sctpsock::{BindOp, RawSocketAddr, SctpSocket};
 
+#[cfg(target_os = "linux")]
+use std::collections::HashMap;
+use std::collections::{BTreeSet, HashSet};
 use std::io::prelude::*;
 use std::io::{Error, ErrorKind, Result};
 use std::net::{Shutdown, SocketAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(target_os = "linux")]
+use std::time::Instant;
 
 #[cfg(target_os = "linux")]
 use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+#[cfg(target_os = "linux")]
+use std::sync::mpsc::Receiver;
+#[cfg(target_os = "linux")]
+use std::thread::JoinHandle;
 
 #[cfg(target_os = "linux")]
 pub mod mio_unix;
 
+#[cfg(target_os = "linux")]
+mod cancel;
+#[cfg(target_os = "linux")]
+pub use cancel::{Cancelled, ShutdownToken};
+
+#[cfg(target_os = "linux")]
+mod errqueue;
+#[cfg(target_os = "linux")]
+pub use errqueue::SctpExtError;
+
+#[cfg(target_os = "linux")]
+mod ffi;
+
+#[cfg(target_os = "linux")]
+mod status;
+#[cfg(target_os = "linux")]
+pub use status::{AssocState, AssocStats, SctpStatus, StreamState};
+
+#[cfg(target_os = "linux")]
+mod association;
+#[cfg(target_os = "linux")]
+pub use association::Association;
+
+#[cfg(target_os = "linux")]
+mod notifications;
+#[cfg(target_os = "linux")]
+pub use notifications::{AssocChangeState, EventSubscription, PeerAddrState, SctpNotification};
+
+#[cfg(target_os = "linux")]
+mod recv;
+#[cfg(target_os = "linux")]
+pub use recv::{IncomingMessage, NextInfo, ReceivedMessage, RecvEvent};
+
+#[cfg(target_os = "linux")]
+mod reconnect;
+#[cfg(target_os = "linux")]
+pub use reconnect::ReconnectingStream;
+
+#[cfg(target_os = "linux")]
+mod prsctp;
+#[cfg(target_os = "linux")]
+pub use prsctp::PrPolicy;
+
+#[cfg(target_os = "linux")]
+mod paddrparams;
+#[cfg(target_os = "linux")]
+pub use paddrparams::PeerAddrParams;
+
+#[cfg(target_os = "linux")]
+mod probe;
+#[cfg(target_os = "linux")]
+pub use probe::{is_available, probe_features, OptionSupport};
+
+#[cfg(target_os = "linux")]
+mod builder;
+#[cfg(target_os = "linux")]
+pub use builder::{ListenerConfig, SctpEndpointBuilder, SctpOptions, SctpStreamBuilder};
+
+#[cfg(all(target_os = "linux", feature = "mio"))]
+mod source;
+
+#[cfg(all(target_os = "linux", feature = "tokio"))]
+mod notification_stream;
+#[cfg(all(target_os = "linux", feature = "tokio"))]
+pub use notification_stream::NotificationStream;
+
+#[cfg(all(target_os = "linux", feature = "trace"))]
+mod trace;
+#[cfg(all(target_os = "linux", feature = "trace"))]
+pub use trace::{clear_trace_hook, set_trace_hook, SyscallTrace};
+
+#[cfg(target_os = "linux")]
+mod priority;
+#[cfg(target_os = "linux")]
+pub use priority::PriorityChannel;
+
+#[cfg(target_os = "linux")]
+mod pool;
+#[cfg(target_os = "linux")]
+pub use pool::{PooledStream, SctpConnectionPool};
+
+pub use sctp_sys::sctp_assoc_t;
+
 #[cfg(target_os = "windows")]
 use std::os::windows::io::{AsRawHandle, FromRawHandle, RawHandle};
 
@@ -35,6 +131,102 @@ use winapi::{
     AF_INET, AF_INET6, SOCK_STREAM, SOL_SOCKET, SO_RCVBUF, SO_RCVTIMEO, SO_SNDBUF, SO_SNDTIMEO,
 };
 
+/// HMAC algorithm identifier for SHA-1, for use with `set_hmac_ident`
+pub const SCTP_AUTH_HMAC_ID_SHA1: u16 = 1;
+/// HMAC algorithm identifier for SHA-256, for use with `set_hmac_ident`
+pub const SCTP_AUTH_HMAC_ID_SHA256: u16 = 3;
+
+/// Resolve a list of addresses, and return the address family a socket must be created with
+/// to use all of them: `AF_INET6` if at least one of them is an IPv6 address, `AF_INET` otherwise
+pub(crate) fn resolve_addrs<A: ToSocketAddrs>(
+    addresses: &[A],
+) -> Result<(libc::c_int, Vec<SocketAddr>)> {
+    if addresses.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
+    }
+    let mut vec = Vec::with_capacity(addresses.len());
+    let mut family = AF_INET;
+    for address in addresses {
+        let a = SocketAddr::from_addr(address)?;
+        if a.family() == AF_INET6 {
+            family = AF_INET6;
+        }
+        vec.push(a);
+    }
+    Ok((family, vec))
+}
+
+/// Get all the IPv4 and IPv6 addresses of the network interface `ifname`, with port `port`
+#[cfg(target_os = "linux")]
+fn interface_addrs(ifname: &str, port: u16) -> Result<Vec<SocketAddr>> {
+    let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+    syscall!(getifaddrs(&mut ifap))?;
+    let mut addrs = Vec::new();
+    let mut ifa = ifap;
+    while !ifa.is_null() {
+        let entry = unsafe { &*ifa };
+        ifa = entry.ifa_next;
+        if entry.ifa_addr.is_null() {
+            continue;
+        }
+        let name = unsafe { std::ffi::CStr::from_ptr(entry.ifa_name) };
+        if name.to_bytes() != ifname.as_bytes() {
+            continue;
+        }
+        // Other families (e.g. AF_PACKET) are skipped
+        if let Ok(mut addr) = unsafe { mio_unix::to_socket_addr(entry.ifa_addr as *const _) } {
+            addr.set_port(port);
+            addrs.push(addr);
+        }
+    }
+    unsafe { libc::freeifaddrs(ifap) };
+    if addrs.is_empty() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            "No IP address found for this interface",
+        ));
+    }
+    Ok(addrs)
+}
+
+/// Create a socket for addresses of `family`. If IPv6 isn't supported by the host, the
+/// error says so instead of the opaque `EAFNOSUPPORT`
+pub(crate) fn new_socket(family: libc::c_int, sock_type: libc::c_int) -> Result<SctpSocket> {
+    SctpSocket::new(family, sock_type).map_err(|err| {
+        if family == AF_INET6 && err.raw_os_error() == Some(libc::EAFNOSUPPORT) {
+            Error::new(ErrorKind::Unsupported, "IPv6 is not supported on this host")
+        } else {
+            err
+        }
+    })
+}
+
+/// Create a socket able to use all the addresses `addrs`, resolved by `resolve_addrs`.
+/// If an IPv6 socket is needed but IPv6 isn't supported by the host, fall back to an IPv4
+/// socket, dropping the IPv6 addresses from `addrs`. Fails if no IPv4 address remains
+pub(crate) fn new_socket_for(
+    family: libc::c_int,
+    addrs: &mut Vec<SocketAddr>,
+    sock_type: libc::c_int,
+) -> Result<SctpSocket> {
+    match new_socket(family, sock_type) {
+        Err(ref err)
+            if err.kind() == ErrorKind::Unsupported && addrs.iter().any(|a| a.is_ipv4()) =>
+        {
+            addrs.retain(|a| a.is_ipv4());
+            new_socket(AF_INET, sock_type)
+        }
+        res => res,
+    }
+}
+
+/// Convert a timeout in whole seconds, as taken by the `set_timeout` methods
+fn timeout_secs(timeout: i32) -> Result<Duration> {
+    u64::try_from(timeout)
+        .map(Duration::from_secs)
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, "Negative timeout"))
+}
+
 /// Socket direction
 pub enum SoDirection {
     /// RCV direction
@@ -62,56 +254,479 @@ impl SoDirection {
 /// One-to-one SCTP connected stream which behaves like a TCP stream.
 /// A `SctpStream` can be obtained either actively by connecting to a SCTP endpoint with the
 /// `connect` constructor, or passively from a `SctpListener` which accepts new connections
-pub struct SctpStream(SctpSocket);
+pub struct SctpStream(SctpSocket, Arc<StreamSettings>);
+
+/// Application-side settings shared by a stream and its clones
+#[derive(Default)]
+struct StreamSettings {
+    /// Set by `pause_recv`
+    recv_paused: AtomicBool,
+    /// Streams on which messages are sent unordered by default, set by `set_stream_unordered`
+    unordered: Mutex<HashSet<u16>>,
+    /// Streams sent or received on, when enabled with `set_track_streams`
+    tracked: Mutex<Option<BTreeSet<u16>>>,
+    /// Eviction priority of the messages sent with `sendmsg_droppable`, per stream,
+    /// lowered by `drop_stream_data`
+    #[cfg(target_os = "linux")]
+    drop_priorities: Mutex<HashMap<u16, u32>>,
+}
 
 impl SctpStream {
+    fn from_socket(sock: SctpSocket) -> SctpStream {
+        SctpStream(sock, Arc::default())
+    }
+
+    /// Record `stream` as active, if tracking is enabled with `set_track_streams`
+    fn track_stream(&self, stream: u16) {
+        if let Some(ref mut streams) = *self.1.tracked.lock().unwrap() {
+            streams.insert(stream);
+        }
+    }
+
+    /// Get the SCTP send flags applied by default to messages sent on `stream`,
+    /// and record it as active
+    fn stream_flags(&self, stream: u16) -> libc::c_int {
+        self.track_stream(stream);
+        if self.1.unordered.lock().unwrap().contains(&stream) {
+            sctp_sys::SCTP_UNORDERED
+        } else {
+            0
+        }
+    }
+
+    fn check_recv_paused(&self) -> Result<()> {
+        if self.1.recv_paused.load(Ordering::Acquire) {
+            return Err(Error::new(ErrorKind::WouldBlock, "Receiving is paused"));
+        }
+        Ok(())
+    }
+
+    /// Create a new stream by connecting it to a remote endpoint.
+    /// Use `SctpStreamBuilder` to set socket options before connecting
+    #[cfg(target_os = "linux")]
+    pub fn connect<A: ToSocketAddrs>(address: A) -> Result<SctpStream> {
+        SctpStreamBuilder::new().connect(address)
+    }
+
     /// Create a new stream by connecting it to a remote endpoint
+    #[cfg(not(target_os = "linux"))]
     pub fn connect<A: ToSocketAddrs>(address: A) -> Result<SctpStream> {
         let raw_addr = SocketAddr::from_addr(&address)?;
-        let sock = SctpSocket::new(raw_addr.family(), SOCK_STREAM)?;
+        let sock = new_socket(raw_addr.family(), SOCK_STREAM)?;
         sock.connect(raw_addr)?;
-        Ok(SctpStream(sock))
+        Ok(SctpStream::from_socket(sock))
     }
 
-    /// Create a new stream by connecting it to a remote endpoint having multiple addresses
+    /// Create a new stream by connecting it to a remote endpoint having multiple addresses.
+    /// On hosts without IPv6 support, IPv6 addresses are ignored.
+    /// Use `SctpStreamBuilder` to set socket options before connecting
+    #[cfg(target_os = "linux")]
     pub fn connectx<A: ToSocketAddrs>(addresses: &[A]) -> Result<SctpStream> {
-        if addresses.is_empty() {
-            return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
-        }
-        let mut vec = Vec::with_capacity(addresses.len());
-        let mut family = AF_INET;
-        for address in addresses {
-            let a = SocketAddr::from_addr(address)?;
-            if a.family() == AF_INET6 {
-                family = AF_INET6;
-            }
-            vec.push(a);
-        }
+        SctpStreamBuilder::new().connectx(addresses)
+    }
 
-        let sock = SctpSocket::new(family, SOCK_STREAM)?;
+    /// Create a new stream by connecting it to a remote endpoint having multiple addresses.
+    /// On hosts without IPv6 support, IPv6 addresses are ignored
+    #[cfg(not(target_os = "linux"))]
+    pub fn connectx<A: ToSocketAddrs>(addresses: &[A]) -> Result<SctpStream> {
+        let (family, mut vec) = resolve_addrs(addresses)?;
+        let sock = new_socket_for(family, &mut vec, SOCK_STREAM)?;
         sock.connectx(&vec)?;
-        Ok(SctpStream(sock))
+        Ok(SctpStream::from_socket(sock))
+    }
+
+    /// Create a new stream bound to all the local addresses `local_addrs`, and connect it to
+    /// `remote`. The association advertises every local address to the peer as a path.
+    /// Local addresses with port 0 share an ephemeral port, non-zero ports must all be the same
+    pub fn multihomed_connect<A: ToSocketAddrs, B: ToSocketAddrs>(
+        local_addrs: &[A],
+        remote: B,
+    ) -> Result<SctpStream> {
+        let (family, locals) = resolve_addrs(local_addrs)?;
+        let remote = SocketAddr::from_addr(&remote)?;
+        let family = if remote.is_ipv6() {
+            libc::AF_INET6
+        } else {
+            family
+        };
+        let sock = new_socket(family, SOCK_STREAM)?;
+        sock.bindx(&locals, BindOp::AddAddr)?;
+        sock.connect(remote)?;
+        Ok(SctpStream::from_socket(sock))
     }
 
     /// Send bytes on the specified SCTP stream. On success, returns the
     /// quantity of bytes read
     pub fn sendmsg(&self, msg: &[u8], stream: u16) -> Result<usize> {
-        self.0.sendmsg::<SocketAddr>(msg, None, 0, stream, 0)
+        let flags = self.stream_flags(stream);
+        self.0
+            .sendmsg_flags::<SocketAddr>(msg, None, 0, flags, stream, 0)
     }
 
     /// Send bytes on the specified SCTP stream. On success, returns the
     /// quantity of bytes read
     pub fn sendmsg_ppid(&self, msg: &[u8], ppid: u32, stream: u16) -> Result<usize> {
-        self.0.sendmsg::<SocketAddr>(msg, None, ppid, stream, 0)
+        let flags = self.stream_flags(stream);
+        self.0
+            .sendmsg_flags::<SocketAddr>(msg, None, ppid, flags, stream, 0)
+    }
+
+    /// Set the stream and payload protocol identifier used by `send_default`.
+    /// Methods taking a stream, like `sendmsg`, pass their parameters explicitly with each
+    /// message: they override these defaults for that message only, without changing them
+    pub fn set_default_send_params(&self, stream: u16, ppid: u32) -> Result<()> {
+        self.0.set_default_send_param(0, stream, ppid)
+    }
+
+    /// Get the stream and payload protocol identifier used by `send_default`
+    pub fn default_send_params(&self) -> Result<(u16, u32)> {
+        self.0.default_send_param(0)
+    }
+
+    /// Send bytes with the default stream and payload protocol identifier set with
+    /// `set_default_send_params`. On success, returns the quantity of bytes sent
+    pub fn send_default(&self, msg: &[u8]) -> Result<usize> {
+        self.0.send_flags(msg, 0)
+    }
+
+    /// Send bytes on the specified SCTP stream with the partial reliability policy `policy`,
+    /// overriding the default one for this message. On success, returns the quantity of
+    /// bytes sent. To drop the messages of a stream while they're still queued, see
+    /// `sendmsg_droppable`
+    #[cfg(target_os = "linux")]
+    pub fn sendmsg_pr(&self, msg: &[u8], stream: u16, policy: PrPolicy) -> Result<usize> {
+        let (flags, value) = policy.to_raw()?;
+        self.0.sendmsg_flags::<SocketAddr>(
+            msg,
+            None,
+            0,
+            flags as libc::c_int | self.stream_flags(stream),
+            stream,
+            value as libc::c_ulong,
+        )
+    }
+
+    /// Send bytes on the specified SCTP stream, so that they can be dropped with
+    /// `drop_stream_data` while still queued. The message is sent with `PrPolicy::Buffer`,
+    /// with the current eviction priority of the stream. On success, returns the quantity
+    /// of bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn sendmsg_droppable(&self, msg: &[u8], stream: u16) -> Result<usize> {
+        let priority = *self
+            .1
+            .drop_priorities
+            .lock()
+            .unwrap()
+            .get(&stream)
+            .unwrap_or(&u32::MAX);
+        self.sendmsg_pr(msg, stream, PrPolicy::Buffer(priority))
+    }
+
+    /// Abandon the messages sent on `stream` with `sendmsg_droppable` which are still queued.
+    ///
+    /// The kernel has no mechanism to abandon the data queued on a stream, so this relies on
+    /// PR-SCTP priority eviction instead: the messages sent afterwards with
+    /// `sendmsg_droppable` on `stream` get a higher priority than the ones already queued.
+    /// This has the following limits:
+    /// * the stale messages are not dropped right away, but when the send buffer is full
+    ///   and a newer message needs room
+    /// * eviction isn't restricted to a stream: droppable messages of other streams with a
+    ///   lower priority may be evicted as well
+    /// * messages sent by other means than `sendmsg_droppable` are never evicted
+    /// * the peer must support PR-SCTP, otherwise nothing is evicted
+    ///
+    /// Fails with `ErrorKind::Other` once the priorities of the stream are exhausted,
+    /// after `u32::MAX` calls
+    #[cfg(target_os = "linux")]
+    pub fn drop_stream_data(&self, stream: u16) -> Result<()> {
+        let mut priorities = self.1.drop_priorities.lock().unwrap();
+        let priority = priorities.entry(stream).or_insert(u32::MAX);
+        if *priority == 0 {
+            return Err(Error::other("No eviction priority left for this stream"));
+        }
+        *priority -= 1;
+        Ok(())
+    }
+
+    /// Send bytes on the specified SCTP stream, letting the stack abandon them if they
+    /// couldn't be delivered within `lifetime` (millisecond precision). This is the same as
+    /// `sendmsg_pr` with `PrPolicy::Ttl`. Requires the peer to support PR-SCTP, otherwise the
+    /// message is delivered reliably. On success, returns the quantity of bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn sendmsg_timed(&self, msg: &[u8], stream: u16, lifetime: Duration) -> Result<usize> {
+        self.sendmsg_pr(msg, stream, PrPolicy::Ttl(lifetime))
+    }
+
+    /// Send bytes on the specified SCTP stream, letting the stack abandon them if they
+    /// couldn't be delivered by `deadline`. The remaining time is computed when sending, and
+    /// passed as the PR-SCTP lifetime of `sendmsg_timed`. If less than a millisecond is
+    /// left, nothing is sent and `ErrorKind::TimedOut` is returned.
+    /// On success, returns the quantity of bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_deadline(&self, msg: &[u8], stream: u16, deadline: Instant) -> Result<usize> {
+        let lifetime = deadline.saturating_duration_since(Instant::now());
+        // A zero lifetime would mean no lifetime at all
+        if lifetime < Duration::from_millis(1) {
+            return Err(Error::new(
+                ErrorKind::TimedOut,
+                "Send deadline already passed",
+            ));
+        }
+        self.sendmsg_timed(msg, stream, lifetime)
+    }
+
+    /// Send bytes on the specified SCTP stream, unordered (`SCTP_UNORDERED`): the peer
+    /// delivers them as soon as they arrive, without head-of-line blocking behind earlier
+    /// messages of the stream. On success, returns the quantity of bytes sent
+    pub fn sendmsg_unordered(&self, msg: &[u8], stream: u16) -> Result<usize> {
+        self.track_stream(stream);
+        self.0
+            .sendmsg_flags::<SocketAddr>(msg, None, 0, sctp_sys::SCTP_UNORDERED, stream, 0)
+    }
+
+    /// Send bytes on the default SCTP stream with the `MSG_MORE` flag, telling the kernel
+    /// more data is coming so that small messages get bundled into fewer packets. The
+    /// bundled data is flushed by the next send without this flag. Linux specific.
+    /// On success, returns the quantity of bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_more(&self, msg: &[u8]) -> Result<usize> {
+        self.0.send_flags(msg, libc::MSG_MORE)
+    }
+
+    /// Enable or disable zero-copy sends with `send_zerocopy` (`SO_ZEROCOPY`). Fails with
+    /// `ErrorKind::Unsupported` if the kernel doesn't support zero-copy on SCTP sockets.
+    /// Zero-copy sends were introduced for TCP in Linux 4.14, but no Linux version
+    /// supports them on SCTP sockets so far
+    #[cfg(target_os = "linux")]
+    pub fn set_zerocopy(&self, on: bool) -> Result<()> {
+        self.0.set_zerocopy(on)
+    }
+
+    /// Send bytes on the specified SCTP stream without copying them into the kernel
+    /// (`MSG_ZEROCOPY`), once enabled with `set_zerocopy`. The kernel keeps referencing `msg`
+    /// after this call returns, so it must not be modified until the send is reported as
+    /// completed by a `recv_error` notification (see `SctpExtError::zerocopy_completed`).
+    /// On success, returns the quantity of bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_zerocopy(&self, msg: &[u8], stream: u16) -> Result<usize> {
+        self.0.send_zerocopy(msg, stream)
+    }
+
+    /// Send bytes on the specified SCTP stream, then wait until all the data sent on the
+    /// association has been acknowledged by the peer, failing with `ErrorKind::TimedOut`
+    /// if it's not the case after `timeout`. Sent chunks are only released from the send
+    /// buffer once acknowledged, so this waits for the send buffer to be empty
+    /// (`SO_MEMINFO`). Acknowledgement only confirms receipt by the peer's SCTP stack, not
+    /// that the application processed the message
+    #[cfg(target_os = "linux")]
+    pub fn send_confirmed(&self, msg: &[u8], stream: u16, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        self.sendmsg(msg, stream)?;
+        loop {
+            if self.0.send_queued()? == 0 {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Error::new(
+                    ErrorKind::TimedOut,
+                    "Message was not acknowledged in time",
+                ));
+            }
+            std::thread::sleep(std::cmp::min(deadline - now, Duration::from_millis(10)));
+        }
+    }
+
+    /// Send bytes on the specified SCTP stream, over the path to `dest` rather than the
+    /// primary path chosen by the stack (`SCTP_ADDR_OVER`). `dest` must be one of the
+    /// peer's known addresses, as returned by `peer_addrs`. On success, returns the
+    /// quantity of bytes sent
+    pub fn sendmsg_to(&self, msg: &[u8], dest: SocketAddr, stream: u16) -> Result<usize> {
+        self.0
+            .sendmsg_flags(msg, Some(dest), 0, sctp_sys::SCTP_ADDR_OVER, stream, 0)
     }
 
     /// Read bytes. On success, return a tuple with the quantity of
-    /// bytes received and the stream they were recived on
+    /// bytes received and the stream they were recived on.
+    /// Use `recvmsg_info` to tell whether a whole message or a notification was received
     pub fn recvmsg(&self, msg: &mut [u8]) -> Result<(usize, u16)> {
+        self.check_recv_paused()?;
         let (size, stream, _) = self.0.recvmsg(msg)?;
+        self.track_stream(stream);
         Ok((size, stream))
     }
 
+    /// Wait for data to be received. On success, returns the quantity of bytes received,
+    /// the stream and payload protocol identifier they were received with, and the peer address.
+    /// When `SCTP_RCVINFO` is enabled with `set_recv_rcvinfo`, the association id is also
+    /// provided, to be correlated with the one of notifications
+    pub fn recvmsg_info(&self, msg: &mut [u8]) -> Result<RecvInfo> {
+        self.check_recv_paused()?;
+        let info = self.0.recvmsg_info(msg)?;
+        self.track_stream(info.stream);
+        Ok(info)
+    }
+
+    /// Same as `recvmsg`, but return early with an `ErrorKind::Interrupted` error
+    /// if `token` is triggered while waiting for data
+    #[cfg(target_os = "linux")]
+    pub fn recvmsg_cancellable(
+        &self,
+        msg: &mut [u8],
+        token: &ShutdownToken,
+    ) -> Result<(usize, u16)> {
+        self.check_recv_paused()?;
+        self.0.wait_readable(token)?;
+        self.recvmsg(msg)
+    }
+
+    /// Pause receiving on this stream, to apply backpressure on the peer. There's no kernel
+    /// mechanism to stop an association from accepting data, so pausing only stops this
+    /// stream (and its clones) from reading: while paused, receive methods fail with
+    /// `ErrorKind::WouldBlock`. Incoming data stays queued in the socket receive buffer,
+    /// shrinking the receive window advertised to the peer until it closes, at which point
+    /// the peer stops sending
+    pub fn pause_recv(&self) {
+        self.1.recv_paused.store(true, Ordering::Release);
+    }
+
+    /// Resume receiving on a stream paused with `pause_recv`. Reading the queued data
+    /// reopens the receive window advertised to the peer
+    pub fn resume_recv(&self) {
+        self.1.recv_paused.store(false, Ordering::Release);
+    }
+
+    /// Check if receiving is paused on this stream
+    pub fn is_recv_paused(&self) -> bool {
+        self.1.recv_paused.load(Ordering::Acquire)
+    }
+
+    /// Choose whether messages sent on `stream` with `sendmsg`, `sendmsg_ppid` or
+    /// `sendmsg_pr` are unordered by default. This is an application-side setting, which
+    /// can be changed at any time: it only affects the messages sent afterwards, not the
+    /// ones already queued or in flight. It is shared with the clones of this stream
+    pub fn set_stream_unordered(&self, stream: u16, unordered: bool) {
+        let mut streams = self.1.unordered.lock().unwrap();
+        if unordered {
+            streams.insert(stream);
+        } else {
+            streams.remove(&stream);
+        }
+    }
+
+    /// Check if messages sent on `stream` are unordered by default
+    pub fn is_stream_unordered(&self, stream: u16) -> bool {
+        self.1.unordered.lock().unwrap().contains(&stream)
+    }
+
+    /// Enable or disable the tracking of the streams used by `sendmsg`, `sendmsg_ppid`,
+    /// `sendmsg_pr`, `sendmsg_unordered`, `recvmsg` and `recvmsg_info`, as reported by
+    /// `active_streams`. The kernel keeps no such record, this is an application-side
+    /// convenience shared with the clones of this stream. Disabling it forgets the streams
+    /// recorded so far
+    pub fn set_track_streams(&self, enable: bool) {
+        let mut tracked = self.1.tracked.lock().unwrap();
+        if !enable {
+            *tracked = None;
+        } else if tracked.is_none() {
+            *tracked = Some(BTreeSet::new());
+        }
+    }
+
+    /// Get the streams sent or received on since tracking was enabled with
+    /// `set_track_streams`, in ascending order. Empty when tracking is disabled
+    pub fn active_streams(&self) -> Vec<u16> {
+        match *self.1.tracked.lock().unwrap() {
+            Some(ref streams) => streams.iter().copied().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Receive either data or a notification. Data is written in `buf`, while notifications
+    /// are decoded into `RecvEvent::Notification`. `buf` must be large enough to hold a whole
+    /// notification, otherwise it fails to be decoded
+    #[cfg(target_os = "linux")]
+    pub fn recv_event(&self, buf: &mut [u8]) -> Result<RecvEvent> {
+        self.check_recv_paused()?;
+        let (len, stream, addr, flags) = self.0.recvmsg_with_flags(buf)?;
+        RecvEvent::new(buf, len, stream, addr, flags)
+    }
+
+    /// Set the maximum size of received messages, to protect against peers sending huge
+    /// messages. 0 means no limit, the default. When a message grows past the limit, the rest
+    /// of it is read and dropped, and the receive fails with `ErrorKind::InvalidData`. The
+    /// parts of the message returned before the limit was reached are not withdrawn.
+    ///
+    /// The limit is enforced by `read`, `recv_full` and `recvmsg_info`. It isn't by
+    /// `recvmsg`, `recvmsg_cancellable` and `recv_event`, which go through `sctp_recvmsg`
+    #[cfg(target_os = "linux")]
+    pub fn set_max_recv_message_size(&self, bytes: usize) {
+        self.0.set_max_recv_message_size(bytes)
+    }
+
+    /// Get the maximum size of received messages, 0 for no limit
+    #[cfg(target_os = "linux")]
+    pub fn max_recv_message_size(&self) -> usize {
+        self.0.max_recv_message_size()
+    }
+
+    /// Receive a message along with every piece of information the kernel provides about it:
+    /// stream, ppid, flags, association id, peer address, and if enabled, reception
+    /// timestamp and next message info
+    #[cfg(target_os = "linux")]
+    pub fn recv_full(&self, buf: &mut [u8]) -> Result<ReceivedMessage> {
+        self.check_recv_paused()?;
+        self.0.recv_full(buf)
+    }
+
+    /// Enable or disable the reception of `SCTP_RCVINFO` ancillary data, which fills
+    /// the stream, ppid and association fields of `recv_full` results
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_rcvinfo(&self, on: bool) -> Result<()> {
+        self.0.set_recv_rcvinfo(on)
+    }
+
+    /// Enable or disable the reception of information about the next message in `recv_full` results
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_nxtinfo(&self, on: bool) -> Result<()> {
+        self.0.set_recv_nxtinfo(on)
+    }
+
+    /// Enable or disable reception timestamps in `recv_full` results
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_timestamp(&self, on: bool) -> Result<()> {
+        self.0.set_recv_timestamp(on)
+    }
+
+    /// Check if the stream is bound to `address`. A port of 0 matches any port
+    pub fn is_bound_to<A: ToSocketAddrs>(&self, address: A) -> Result<bool> {
+        self.0.is_bound_to(SocketAddr::from_addr(&address)?)
+    }
+
+    /// Add local addresses to the stream at runtime. Fails if one of them is already bound
+    pub fn bindx_add<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<()> {
+        self.0.bindx_add(&resolve_addrs(addresses)?.1)
+    }
+
+    /// Remove local addresses from the stream at runtime. Fails if one of them is not bound,
+    /// if it would leave the stream without any address, or without an address of the same
+    /// family as the primary destination of the peer
+    pub fn bindx_remove<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<()> {
+        self.0.bindx_remove(&resolve_addrs(addresses)?.1)
+    }
+
+    /// Subscribe to SCTP events
+    #[cfg(target_os = "linux")]
+    pub fn subscribe_events(&self, events: &EventSubscription) -> Result<()> {
+        self.0.subscribe_events(events)
+    }
+
+    /// Get the SCTP events this stream is subscribed to
+    #[cfg(target_os = "linux")]
+    pub fn events(&self) -> Result<EventSubscription> {
+        self.0.events()
+    }
+
     /// Return the list of local socket addresses for this stream
     pub fn local_addrs(&self) -> Result<Vec<SocketAddr>> {
         self.0.local_addrs(0)
@@ -122,6 +737,41 @@ impl SctpStream {
         self.0.peer_addrs(0)
     }
 
+    /// Set the parameters of the path to the peer address `addr` (heartbeats, maximum
+    /// retransmissions, path MTU), or the default parameters of the association if `None`
+    #[cfg(target_os = "linux")]
+    pub fn set_peer_addr_params(
+        &self,
+        addr: Option<SocketAddr>,
+        params: PeerAddrParams,
+    ) -> Result<()> {
+        self.0.set_peer_addr_params(0, addr.as_ref(), &params)
+    }
+
+    /// Get the parameters of the path to the peer address `addr`, or the default
+    /// parameters of the association if `None`
+    #[cfg(target_os = "linux")]
+    pub fn peer_addr_params(&self, addr: Option<SocketAddr>) -> Result<PeerAddrParams> {
+        self.0.peer_addr_params(0, addr.as_ref())
+    }
+
+    /// Get the smallest path MTU among all the paths of the association. Messages fitting
+    /// in it are not fragmented, whichever path they are sent on
+    #[cfg(target_os = "linux")]
+    pub fn min_path_mtu(&self) -> Result<u32> {
+        self.0.min_path_mtu(0)
+    }
+
+    /// Get the local port of this stream
+    pub fn local_port(&self) -> Result<u16> {
+        Ok(self.0.sockname()?.port())
+    }
+
+    /// Get the port of the remote peer
+    pub fn peer_port(&self) -> Result<u16> {
+        Ok(self.0.peername()?.port())
+    }
+
     /// Shuts down the read, write, or both halves of this connection
     pub fn shutdown(&self, how: Shutdown) -> Result<()> {
         self.0.shutdown(how)
@@ -139,6 +789,50 @@ impl SctpStream {
         Ok(val == 1)
     }
 
+    /// Set the initial, maximum and minimum retransmission timeouts of the association, in
+    /// milliseconds. Fails with `ErrorKind::InvalidInput` unless `min <= initial <= max`
+    #[cfg(target_os = "linux")]
+    pub fn set_rto_info(&self, initial: u32, max: u32, min: u32) -> Result<()> {
+        self.0.set_rto_info(0, initial, max, min)
+    }
+
+    /// Get the initial, maximum and minimum retransmission timeouts of the association, in
+    /// milliseconds
+    #[cfg(target_os = "linux")]
+    pub fn get_rto_info(&self) -> Result<(u32, u32, u32)> {
+        self.0.rto_info(0)
+    }
+
+    /// Set the largest user message fragment put in a DATA chunk (`SCTP_MAXSEG`), in bytes,
+    /// e.g. to fit a constrained-MTU link. A `size` of 0 restores the default, which
+    /// follows the path MTU
+    #[cfg(target_os = "linux")]
+    pub fn set_max_seg(&self, size: u32) -> Result<()> {
+        self.0.set_max_seg(0, size)
+    }
+
+    /// Get the effective fragmentation point of the association (`SCTP_MAXSEG`), in bytes
+    #[cfg(target_os = "linux")]
+    pub fn get_max_seg(&self) -> Result<u32> {
+        self.0.max_seg(0)
+    }
+
+    /// Configure delayed SACKs: a SACK is sent after `freq` packets, or after `delay` if
+    /// fewer packets were received. Use a zero `delay` with `freq` 1 to acknowledge every
+    /// packet immediately, or a `delay` between 1 and 500 ms with `freq` 2 or more.
+    /// Other combinations fail with `ErrorKind::InvalidInput`
+    #[cfg(target_os = "linux")]
+    pub fn set_delayed_sack(&self, delay: Duration, freq: u32) -> Result<()> {
+        self.0.set_delayed_sack(0, delay, freq)
+    }
+
+    /// Get the delayed SACK delay and frequency. A frequency of 1 means delayed SACKs
+    /// are disabled
+    #[cfg(target_os = "linux")]
+    pub fn delayed_sack(&self) -> Result<(Duration, u32)> {
+        self.0.delayed_sack(0)
+    }
+
     /// Set the socket buffer size for the direction specified by `dir`.
     /// Linux systems will double the provided size
     pub fn set_buffer_size(&self, dir: SoDirection, size: usize) -> Result<()> {
@@ -154,23 +848,367 @@ impl SctpStream {
 
     /// Set `timeout` in seconds for operation `dir` (either receive or send)
     pub fn set_timeout(&self, dir: SoDirection, timeout: i32) -> Result<()> {
-        // Workaround: Use of long instead of libc::time_t which does not compile in windows x86_64
-        let tval = libc::timeval {
-            tv_sec: timeout as libc::c_long,
-            tv_usec: 0,
+        self.set_timeout_dur(dir, Some(timeout_secs(timeout)?))
+    }
+
+    /// Set `timeout` for operation `dir` (either receive or send), with microsecond precision.
+    /// `None` or a zero duration clear the timeout
+    pub fn set_timeout_dur(&self, dir: SoDirection, timeout: Option<Duration>) -> Result<()> {
+        self.0.set_timeout(dir.timeout_opt(), timeout)
+    }
+
+    /// Get the timeout for operation `dir` (either receive or send). `None` means there's
+    /// no timeout
+    pub fn get_timeout(&self, dir: SoDirection) -> Result<Option<Duration>> {
+        self.0.timeout(dir.timeout_opt())
+    }
+
+    /// Bound how long a blocking send waits for room in the send buffer, e.g. when the peer
+    /// doesn't drain its receive window, before failing with `ErrorKind::TimedOut`
+    /// (`SO_SNDTIMEO`). `None` lets sends block forever. Applies to every send method
+    pub fn set_send_block_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        self.0.set_timeout(SoDirection::Send.timeout_opt(), timeout)
+    }
+
+    /// Get the live status of the association
+    #[cfg(target_os = "linux")]
+    pub fn status(&self) -> Result<SctpStatus> {
+        self.0.status(0)
+    }
+
+    /// Get the name of the congestion control algorithm of the association.
+    /// Fails with `ErrorKind::Unsupported` if the kernel doesn't expose it per socket,
+    /// which is the case of Linux
+    #[cfg(target_os = "linux")]
+    pub fn congestion_control(&self) -> Result<String> {
+        self.0.congestion_control(0)
+    }
+
+    /// Select the congestion control algorithm of the association by name (`rfc2581`,
+    /// `hstcp`, `htcp` or `rtcc`). Fails with `ErrorKind::Unsupported` if the kernel
+    /// doesn't allow selecting it per socket, which is the case of Linux
+    #[cfg(target_os = "linux")]
+    pub fn set_congestion_control(&self, name: &str) -> Result<()> {
+        self.0.set_congestion_control(0, name)
+    }
+
+    /// Move the stream into or out of non-blocking mode. In non-blocking mode, receive
+    /// operations return an `ErrorKind::WouldBlock` error instead of blocking when there's no
+    /// data, and send operations when the send buffer is full
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Get the current state of the association
+    #[cfg(target_os = "linux")]
+    pub fn state(&self) -> Result<AssocState> {
+        Ok(self.0.status(0)?.state)
+    }
+
+    /// Get the statistics of the association
+    #[cfg(target_os = "linux")]
+    pub fn assoc_stats(&self) -> Result<AssocStats> {
+        Ok(AssocStats::from_raw(&self.0.assoc_stats(0)?))
+    }
+
+    /// Get the number of inbound and outbound streams negotiated with the peer, as
+    /// `(instreams, outstreams)`. Valid outbound stream ids are below `outstreams`
+    #[cfg(target_os = "linux")]
+    pub fn stream_counts(&self) -> Result<(u16, u16)> {
+        let status = self.0.status(0)?;
+        Ok((status.instreams, status.outstreams))
+    }
+
+    /// Get the best-effort state of `stream`, for debugging ordering issues. Fails with
+    /// `InvalidInput` if the stream is neither a negotiated inbound nor outbound stream.
+    /// Stream sequence numbers are not exposed by Linux, see `StreamState`
+    #[cfg(target_os = "linux")]
+    pub fn stream_state(&self, stream: u16) -> Result<StreamState> {
+        let state = StreamState::from_status(&self.0.status(0)?, stream);
+        if !state.outbound && !state.inbound {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Stream is not part of the association",
+            ));
+        }
+        Ok(state)
+    }
+
+    /// Set the peer address used as primary destination. It must be one of the peer addresses
+    #[cfg(target_os = "linux")]
+    pub fn set_primary_addr<A: ToSocketAddrs>(&self, address: A) -> Result<()> {
+        self.0.set_primary_addr(0, &SocketAddr::from_addr(address)?)
+    }
+
+    /// Set the primary destination to the peer address at `index` in `peer_addrs`.
+    /// Fails with `ErrorKind::InvalidInput` if `index` is out of range
+    #[cfg(target_os = "linux")]
+    pub fn set_primary_path_index(&self, index: usize) -> Result<()> {
+        let addrs = self.peer_addrs()?;
+        let addr = addrs.get(index).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidInput, "Peer address index out of range")
+        })?;
+        self.0.set_primary_addr(0, addr)
+    }
+
+    /// Reset the streams `streams`, in the directions given by `how`: `Shutdown::Read` for
+    /// inbound streams, `Shutdown::Write` for outbound streams, or both. An empty list resets
+    /// all the streams. The stream reconfiguration extension must have been negotiated, by
+    /// enabling it with `SctpStreamBuilder::enable_stream_reset` before connecting
+    #[cfg(target_os = "linux")]
+    pub fn reset_streams(&self, streams: &[u16], how: Shutdown) -> Result<()> {
+        let flags = match how {
+            Shutdown::Read => ffi::SCTP_STREAM_RESET_INCOMING,
+            Shutdown::Write => ffi::SCTP_STREAM_RESET_OUTGOING,
+            Shutdown::Both => ffi::SCTP_STREAM_RESET_INCOMING | ffi::SCTP_STREAM_RESET_OUTGOING,
         };
-        self.0.setsockopt(SOL_SOCKET, dir.timeout_opt(), &tval)
+        self.0.reset_streams(0, streams, flags)
+    }
+
+    /// Add `in_streams` inbound and `out_streams` outbound streams to the association.
+    /// Same prerequisite as `reset_streams`
+    #[cfg(target_os = "linux")]
+    pub fn add_streams(&self, in_streams: u16, out_streams: u16) -> Result<()> {
+        self.0.add_streams(0, in_streams, out_streams)
+    }
+
+    /// Get the largest message which fits in a single packet on the primary path, given its
+    /// current path MTU and the IP, SCTP common and DATA chunk headers overhead.
+    /// Sending messages of at most this size avoids fragmentation
+    #[cfg(target_os = "linux")]
+    pub fn max_unfragmented_payload(&self) -> Result<usize> {
+        let status = self.0.status(0)?;
+        let ip_header = match status.primary_addr {
+            Some(SocketAddr::V6(addr)) if addr.ip().to_ipv4_mapped().is_none() => 40,
+            _ => 20,
+        };
+        // SCTP common header + DATA chunk header
+        let overhead = ip_header + 12 + 16;
+        Ok((status.primary_mtu as usize).saturating_sub(overhead))
+    }
+
+    /// Send `msg` on the specified SCTP stream with the payload protocol identifier `ppid`,
+    /// split into pieces of at most `max_unfragmented_payload` bytes. Each piece is sent as a
+    /// separate ordered message, regardless of `set_stream_unordered`: the receiver gets
+    /// several messages, to be concatenated in order to rebuild `msg`.
+    /// On success, returns the total quantity of bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_chunked(&self, msg: &[u8], stream: u16, ppid: u32) -> Result<usize> {
+        let chunk_size = self.max_unfragmented_payload()?;
+        if chunk_size == 0 {
+            return Err(Error::other("Path MTU too small to carry any payload"));
+        }
+        self.track_stream(stream);
+        let mut sent = 0;
+        for chunk in msg.chunks(chunk_size) {
+            sent += self
+                .0
+                .sendmsg_flags::<SocketAddr>(chunk, None, ppid, 0, stream, 0)?;
+        }
+        Ok(sent)
+    }
+
+    /// Get a normalized 0.0 - 1.0 estimate of how full the send buffer is, usable as a pacing
+    /// signal. The kernel reports the unacknowledged and pending data as chunk counts, so they
+    /// are converted to bytes using the fragmentation point, giving an upper bound. The ratio is
+    /// computed against the send buffer size as reported by the kernel, which Linux doubles
+    /// compared to the size set with `set_buffer_size`
+    #[cfg(target_os = "linux")]
+    pub fn send_buffer_pressure(&self) -> Result<f32> {
+        let status = self.0.status(0)?;
+        let sndbuf = self.get_buffer_size(SoDirection::Send)?;
+        if sndbuf == 0 {
+            return Ok(0.0);
+        }
+        let chunks = status.unacked_data as f32 + status.pending_data as f32;
+        let bytes = chunks * status.fragmentation_point as f32;
+        Ok((bytes / sndbuf as f32).min(1.0))
+    }
+
+    /// Wait until the send buffer has room for at least `bytes` bytes, for at most `timeout`.
+    /// Fails with `ErrorKind::TimedOut` if there's still not enough room after `timeout`.
+    /// This is an approximation: the room is estimated as the send buffer size (`SO_SNDBUF`)
+    /// minus the number of unacknowledged chunks times the fragmentation point. The kernel
+    /// only refreshes the number of unacknowledged chunks when a SACK arrives, so the
+    /// estimate lags behind the most recent sends
+    #[cfg(target_os = "linux")]
+    pub fn wait_send_capacity(&self, bytes: usize, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if self.0.wait_writable_timeout(Some(remaining))? {
+                let status = self.0.status(0)?;
+                let sndbuf = self.get_buffer_size(SoDirection::Send)?;
+                let used = status.unacked_data as usize * status.fragmentation_point as usize;
+                if sndbuf.saturating_sub(used) >= bytes {
+                    return Ok(());
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::new(
+                    ErrorKind::TimedOut,
+                    "Not enough room in the send buffer",
+                ));
+            }
+            std::thread::sleep(std::cmp::min(
+                Duration::from_millis(10),
+                deadline.saturating_duration_since(Instant::now()),
+            ));
+        }
+    }
+
+    /// Actively check that the peer is alive, by requesting an immediate heartbeat on the
+    /// primary path and waiting for up to `timeout` for the peer to answer. Any control
+    /// chunk received from the peer in the meantime counts as an answer. Returns `false`
+    /// if the peer didn't answer in time or the association is no longer established
+    #[cfg(target_os = "linux")]
+    pub fn is_peer_alive(&self, timeout: Duration) -> Result<bool> {
+        let deadline = Instant::now() + timeout;
+        let received = self.0.assoc_stats(0)?.sas_ictrlchunks;
+        let primary = self.0.status(0)?.primary_addr.ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotConnected,
+                "Association has no primary address",
+            )
+        })?;
+        self.0.request_heartbeat(0, &primary)?;
+        loop {
+            if self.0.assoc_stats(0)?.sas_ictrlchunks > received {
+                return Ok(true);
+            }
+            if self.0.status(0)?.state != AssocState::Established {
+                return Ok(false);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(false);
+            }
+            std::thread::sleep(std::cmp::min(deadline - now, Duration::from_millis(10)));
+        }
+    }
+
+    /// Get the number of destination addresses advertised by the peer
+    #[cfg(target_os = "linux")]
+    pub fn peer_destination_count(&self) -> Result<u16> {
+        Ok(self.0.associnfo(0)?.sasoc_number_peer_destinations)
+    }
+
+    /// Set how many retransmissions are allowed on the path to `address` before it is
+    /// marked as failed. Lowering it makes the association fail over faster to another
+    /// peer address when the path is flaky. `address` must be one of the peer addresses
+    #[cfg(target_os = "linux")]
+    pub fn set_path_max_retransmits<A: ToSocketAddrs>(&self, address: A, count: u16) -> Result<()> {
+        self.0.set_path_max_retransmits(0, address, count)
+    }
+
+    /// Enable or disable reception of ICMP-derived path errors in the socket error queue
+    /// (`IP_RECVERR` / `IPV6_RECVERR`)
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_err(&self, on: bool) -> Result<()> {
+        self.0.set_recv_err(on)
+    }
+
+    /// Read the next extended error from the socket error queue without blocking.
+    /// Returns `None` if there's no pending error
+    #[cfg(target_os = "linux")]
+    pub fn recv_error(&self) -> Result<Option<SctpExtError>> {
+        self.0.recv_error()
+    }
+
+    /// Set the partial reliability policy applied by default to sent messages
+    /// (`SCTP_DEFAULT_PRINFO`). Once set, plain sends may be abandoned according to `policy`
+    /// if the peer supports PR-SCTP. The kernel only applies it to messages sent without
+    /// explicit send parameters, i.e. with `write` or `send_default`. Methods taking a stream,
+    /// like `sendmsg`, pass their parameters with each message and ignore this default:
+    /// use `sendmsg_pr` for them instead
+    #[cfg(target_os = "linux")]
+    pub fn set_default_pr(&self, policy: PrPolicy) -> Result<()> {
+        self.0.set_default_prinfo(0, policy)
+    }
+
+    /// Get the partial reliability policy applied by default to sent messages
+    #[cfg(target_os = "linux")]
+    pub fn default_pr(&self) -> Result<PrPolicy> {
+        self.0.default_prinfo(0)
+    }
+
+    /// Enable or disable kernel socket debug tracing (`SO_DEBUG`), to be used together
+    /// with tools like `ss`. Requires privileges (`CAP_NET_ADMIN` on Linux), and
+    /// fails with `ErrorKind::PermissionDenied` when not permitted
+    pub fn set_debug(&self, on: bool) -> Result<()> {
+        self.0.set_debug(on)
+    }
+
+    /// Choose whether sends go through `sendmsg(2)` with an `SCTP_SNDINFO` control message,
+    /// which is the default on Linux, or through the deprecated `sctp_sendmsg` function.
+    /// Sends with a lifetime always use `sctp_sendmsg`, as `SCTP_SNDINFO` can't carry it
+    #[cfg(target_os = "linux")]
+    pub fn prefer_modern_api(&self, modern: bool) {
+        self.0.prefer_modern_api(modern)
+    }
+
+    /// Wait for the next SCTP notification, for at most `timeout` if not `None`, returning
+    /// `None` on timeout. Notifications must have been subscribed to with `EventSubscription`.
+    /// Data messages are never consumed, so this is meant for sockets whose data is handled
+    /// elsewhere (e.g. on peeled off associations), and must be the only consumer of the
+    /// receive queue. When a data message is at its head, it is left there and this fails
+    /// with `ErrorKind::InvalidData`. At end of file, this fails with
+    /// `ErrorKind::UnexpectedEof`. To receive notifications while data is handled on the
+    /// same socket, use `spawn_notification_handler`, which delivers both
+    #[cfg(target_os = "linux")]
+    pub fn recv_notification(&self, timeout: Option<Duration>) -> Result<Option<SctpNotification>> {
+        self.0.recv_notification(timeout)
+    }
+
+    /// Set or unset the close-on-exec flag, telling whether the socket is closed in child
+    /// processes on `exec`. Sockets created by this crate have it set
+    #[cfg(target_os = "linux")]
+    pub fn set_cloexec(&self, cloexec: bool) -> Result<()> {
+        self.0.set_cloexec(cloexec)
+    }
+
+    /// Check if the socket is in non-blocking mode
+    #[cfg(target_os = "linux")]
+    pub fn is_nonblocking(&self) -> Result<bool> {
+        self.0.is_nonblocking()
+    }
+
+    /// Spawn a thread handling the SCTP notifications of this socket, and return its join
+    /// handle along with the channel they are delivered into. The socket is subscribed to
+    /// every notification. The data messages read by the thread are delivered into the
+    /// channel too, so the other threads can keep receiving on the socket without losing
+    /// any. The thread runs on a clone of the socket and stops once the channel is dropped,
+    /// at end of file, or on a receive error
+    #[cfg(target_os = "linux")]
+    pub fn spawn_notification_handler(
+        &self,
+    ) -> Result<(JoinHandle<Result<()>>, Receiver<IncomingMessage>)> {
+        notifications::spawn_handler(self.0.try_clone()?)
+    }
+
+    /// Get an asynchronous stream of the SCTP notifications of this socket, driven by the
+    /// Tokio reactor. The socket is subscribed to every notification, and the data messages
+    /// read by the stream are yielded too. The stream runs on a clone of the socket, and
+    /// must be created from within a Tokio runtime
+    #[cfg(all(target_os = "linux", feature = "tokio"))]
+    pub fn notification_stream(&self) -> Result<NotificationStream> {
+        NotificationStream::new(self.0.try_clone()?)
     }
 
     /// Try to clone the SctpStream. On success, returns a new stream
     /// wrapping a new socket handler
     pub fn try_clone(&self) -> Result<SctpStream> {
-        Ok(SctpStream(self.0.try_clone()?))
+        Ok(SctpStream(self.0.try_clone()?, self.1.clone()))
     }
 }
 
 impl Read for SctpStream {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.check_recv_paused()?;
+        // Go through recvmsg, to enforce the maximum message size
+        #[cfg(target_os = "linux")]
+        return self.0.recv_full(buf).map(|received| received.bytes);
+        #[cfg(not(target_os = "linux"))]
         self.0.recv(buf)
     }
 }
@@ -195,7 +1233,7 @@ impl AsRawHandle for SctpStream {
 #[cfg(target_os = "windows")]
 impl FromRawHandle for SctpStream {
     unsafe fn from_raw_handle(hdl: RawHandle) -> SctpStream {
-        SctpStream(SctpSocket::from_raw_handle(hdl))
+        SctpStream::from_socket(SctpSocket::from_raw_handle(hdl))
     }
 }
 
@@ -209,7 +1247,7 @@ impl AsRawFd for SctpStream {
 #[cfg(target_os = "linux")]
 impl FromRawFd for SctpStream {
     unsafe fn from_raw_fd(fd: RawFd) -> SctpStream {
-        SctpStream(SctpSocket::from_raw_fd(fd))
+        SctpStream::from_socket(SctpSocket::from_raw_fd(fd))
     }
 }
 
@@ -220,28 +1258,17 @@ impl SctpEndpoint {
     /// Create a one-to-many SCTP endpoint bound to a single address
     pub fn bind<A: ToSocketAddrs>(address: A) -> Result<SctpEndpoint> {
         let raw_addr = SocketAddr::from_addr(&address)?;
-        let sock = SctpSocket::new(raw_addr.family(), SOCK_SEQPACKET)?;
+        let sock = new_socket(raw_addr.family(), SOCK_SEQPACKET)?;
         sock.bind(raw_addr)?;
         sock.listen(-1)?;
         Ok(SctpEndpoint(sock))
     }
 
-    /// Create a one-to-many SCTP endpoint bound to a multiple addresses. Requires at least one address
+    /// Create a one-to-many SCTP endpoint bound to a multiple addresses. Requires at least one address.
+    /// On hosts without IPv6 support, IPv6 addresses are ignored
     pub fn bindx<A: ToSocketAddrs>(addresses: &[A]) -> Result<SctpEndpoint> {
-        if addresses.is_empty() {
-            return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
-        }
-        let mut vec = Vec::with_capacity(addresses.len());
-        let mut family = AF_INET;
-        for address in addresses {
-            let a = SocketAddr::from_addr(address)?;
-            if a.family() == AF_INET6 {
-                family = AF_INET6;
-            }
-            vec.push(a);
-        }
-
-        let sock = SctpSocket::new(family, SOCK_SEQPACKET)?;
+        let (family, mut vec) = resolve_addrs(addresses)?;
+        let sock = new_socket_for(family, &mut vec, SOCK_SEQPACKET)?;
         sock.bindx(&vec, BindOp::AddAddr)?;
         sock.listen(-1)?;
         Ok(SctpEndpoint(sock))
@@ -254,6 +1281,122 @@ impl SctpEndpoint {
         self.0.recvmsg(msg)
     }
 
+    /// Wait for data to be received. On success, returns the quantity of bytes received,
+    /// the stream and payload protocol identifier they were received with, and the address
+    /// of the peer which sent them
+    pub fn recv_from_info(&self, msg: &mut [u8]) -> Result<RecvInfo> {
+        self.0.recvmsg_info(msg)
+    }
+
+    /// Wait for data to be received. On success, returns the quantity of bytes received, the
+    /// stream they were received on, the id of the association they belong to, and the address
+    /// of the peer which sent them. The association id is only known when data I/O events are
+    /// subscribed to or `SCTP_RCVINFO` is enabled (see `set_recv_rcvinfo`), 0 otherwise
+    pub fn recv_from_assoc(
+        &self,
+        msg: &mut [u8],
+    ) -> Result<(usize, u16, sctp_assoc_t, SocketAddr)> {
+        let info = self.0.recvmsg_info(msg)?;
+        Ok((info.bytes, info.stream, info.assoc_id, info.addr))
+    }
+
+    /// Receive up to `bufs.len()` messages in one call, waiting for at most `timeout` if not
+    /// `None` for the first one, and then only reading messages immediately available.
+    /// The stream, ppid, association and sender of `bufs[i]` are given by `infos[i]`.
+    /// Returns the number of buffers filled, 0 on timeout
+    #[cfg(target_os = "linux")]
+    pub fn recv_batch(
+        &self,
+        bufs: &mut [&mut [u8]],
+        infos: &mut Vec<RecvInfo>,
+        timeout: Option<Duration>,
+    ) -> Result<usize> {
+        self.0.recv_batch(bufs, infos, timeout)
+    }
+
+    /// Receive up to `bufs.len()` messages with a single system call (`recvmmsg`), blocking
+    /// until at least one is available. Each buffer receives one message, or a part of it
+    /// if it's too small. Returns one `ReceivedMessage` per filled buffer
+    #[cfg(target_os = "linux")]
+    pub fn recvmmsg(&self, bufs: &mut [&mut [u8]]) -> Result<Vec<ReceivedMessage>> {
+        self.0.recvmmsg(bufs, libc::MSG_WAITFORONE)
+    }
+
+    /// Send several messages with a single system call (`sendmmsg`), each one to its own
+    /// destination and stream. Returns the quantity of bytes sent for each message actually
+    /// sent, which may be fewer than `msgs.len()`
+    #[cfg(target_os = "linux")]
+    pub fn sendmmsg(&self, msgs: &[BatchMessage<'_>]) -> Result<Vec<usize>> {
+        self.0.sendmmsg(msgs, 0)
+    }
+
+    /// Same as `recv_from`, but return early with an `ErrorKind::Interrupted` error
+    /// if `token` is triggered while waiting for data
+    #[cfg(target_os = "linux")]
+    pub fn recv_from_cancellable(
+        &self,
+        msg: &mut [u8],
+        token: &ShutdownToken,
+    ) -> Result<(usize, u16, SocketAddr)> {
+        self.0.wait_readable(token)?;
+        self.0.recvmsg(msg)
+    }
+
+    /// Receive either data or a notification. Data is written in `buf`, while notifications
+    /// are decoded into `RecvEvent::Notification`. `buf` must be large enough to hold a whole
+    /// notification, otherwise it fails to be decoded
+    #[cfg(target_os = "linux")]
+    pub fn recv_event(&self, buf: &mut [u8]) -> Result<RecvEvent> {
+        let (len, stream, addr, flags) = self.0.recvmsg_with_flags(buf)?;
+        RecvEvent::new(buf, len, stream, addr, flags)
+    }
+
+    /// Set the maximum size of received messages, to protect against peers sending huge
+    /// messages. 0 means no limit, the default. When a message grows past the limit, the rest
+    /// of it is read and dropped, and the receive fails with `ErrorKind::InvalidData`. The
+    /// parts of the message returned before the limit was reached are not withdrawn.
+    ///
+    /// The limit is enforced by `recv_full`, `recv_from_info`, `recv_from_assoc` and
+    /// `recv_batch`. It isn't by `recv_from`, `recv_from_cancellable` and `recv_event`,
+    /// which go through `sctp_recvmsg`, nor by `recvmmsg`
+    #[cfg(target_os = "linux")]
+    pub fn set_max_recv_message_size(&self, bytes: usize) {
+        self.0.set_max_recv_message_size(bytes)
+    }
+
+    /// Get the maximum size of received messages, 0 for no limit
+    #[cfg(target_os = "linux")]
+    pub fn max_recv_message_size(&self) -> usize {
+        self.0.max_recv_message_size()
+    }
+
+    /// Receive a message along with every piece of information the kernel provides about it:
+    /// stream, ppid, flags, association id, peer address, and if enabled, reception
+    /// timestamp and next message info
+    #[cfg(target_os = "linux")]
+    pub fn recv_full(&self, buf: &mut [u8]) -> Result<ReceivedMessage> {
+        self.0.recv_full(buf)
+    }
+
+    /// Enable or disable the reception of `SCTP_RCVINFO` ancillary data, which fills
+    /// the stream, ppid and association fields of `recv_full` results
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_rcvinfo(&self, on: bool) -> Result<()> {
+        self.0.set_recv_rcvinfo(on)
+    }
+
+    /// Enable or disable the reception of information about the next message in `recv_full` results
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_nxtinfo(&self, on: bool) -> Result<()> {
+        self.0.set_recv_nxtinfo(on)
+    }
+
+    /// Enable or disable reception timestamps in `recv_full` results
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_timestamp(&self, on: bool) -> Result<()> {
+        self.0.set_recv_timestamp(on)
+    }
+
     /// Send data in Sctp style, to the provided address on the stream `stream`.
     /// On success, returns the quantity on bytes sent
     pub fn send_to<A: ToSocketAddrs>(
@@ -265,11 +1408,222 @@ impl SctpEndpoint {
         self.0.sendmsg(msg, Some(address), 0, stream, 0)
     }
 
+    /// Send data in Sctp style, to the provided address on the stream `stream`, with the
+    /// payload protocol identifier `ppid`. On success, returns the quantity on bytes sent
+    pub fn send_to_ppid<A: ToSocketAddrs>(
+        &self,
+        msg: &[u8],
+        address: A,
+        ppid: u32,
+        stream: u16,
+    ) -> Result<usize> {
+        self.0.sendmsg(msg, Some(address), ppid, stream, 0)
+    }
+
+    /// Send data in Sctp style, unordered (`SCTP_UNORDERED`), to the provided address on the
+    /// stream `stream`. On success, returns the quantity on bytes sent
+    pub fn send_to_unordered<A: ToSocketAddrs>(
+        &self,
+        msg: &[u8],
+        address: A,
+        stream: u16,
+    ) -> Result<usize> {
+        self.0
+            .sendmsg_flags(msg, Some(address), 0, sctp_sys::SCTP_UNORDERED, stream, 0)
+    }
+
+    /// Send data in Sctp style, to the provided address on the stream `stream`, with the
+    /// partial reliability policy `policy` overriding the default one for this message.
+    /// On success, returns the quantity on bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_to_pr<A: ToSocketAddrs>(
+        &self,
+        msg: &[u8],
+        address: A,
+        stream: u16,
+        policy: PrPolicy,
+    ) -> Result<usize> {
+        let (flags, value) = policy.to_raw()?;
+        self.0.sendmsg_flags(
+            msg,
+            Some(address),
+            0,
+            flags as libc::c_int,
+            stream,
+            value as libc::c_ulong,
+        )
+    }
+
+    /// Send data on the stream `stream` of the association `assoc`, rather than to a
+    /// socket address. On success, returns the quantity on bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_to_assoc(&self, msg: &[u8], assoc: sctp_assoc_t, stream: u16) -> Result<usize> {
+        self.association(assoc).send(msg, stream)
+    }
+
+    /// Send data in Sctp style, to the provided address on the stream `stream`. If this
+    /// send creates a new association, it requests `out_streams` outbound streams instead
+    /// of the count set socket-wide with `SCTP_INITMSG`. If the association already exists,
+    /// `out_streams` is ignored. On success, returns the quantity on bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_to_with_streams<A: ToSocketAddrs>(
+        &self,
+        msg: &[u8],
+        address: A,
+        stream: u16,
+        out_streams: u16,
+    ) -> Result<usize> {
+        let addr = SocketAddr::from_addr(address)?;
+        let info = ffi::sctp_sndinfo {
+            snd_sid: stream,
+            snd_flags: 0,
+            snd_ppid: 0,
+            snd_context: 0,
+            snd_assoc_id: 0,
+        };
+        let init = ffi::sctp_initmsg {
+            sinit_num_ostreams: out_streams,
+            ..Default::default()
+        };
+        self.0.send_init(msg, &addr, &info, &init)
+    }
+
+    /// Send data on the stream `stream` of the association `assoc`, over the path to the
+    /// peer address `path` (`SCTP_ADDR_OVER`). `path` must be one of the addresses of the
+    /// association's peer, otherwise an `ErrorKind::InvalidInput` error is returned.
+    /// On success, returns the quantity on bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_to_path(
+        &self,
+        msg: &[u8],
+        assoc: sctp_assoc_t,
+        path: SocketAddr,
+        stream: u16,
+    ) -> Result<usize> {
+        if !self.0.peer_addrs(assoc)?.contains(&path) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Address does not belong to the association",
+            ));
+        }
+        let mut info: sctp_sys::sctp_sndrcvinfo = unsafe { std::mem::zeroed() };
+        info.sinfo_stream = stream;
+        info.sinfo_flags = sctp_sys::SCTP_ADDR_OVER as u16;
+        info.sinfo_assoc_id = assoc;
+        self.0.send_info_to(msg, &path, &info)
+    }
+
+    /// Check if the endpoint is bound to `address`. A port of 0 matches any port
+    pub fn is_bound_to<A: ToSocketAddrs>(&self, address: A) -> Result<bool> {
+        self.0.is_bound_to(SocketAddr::from_addr(&address)?)
+    }
+
+    /// Add local addresses to the endpoint at runtime. Fails if one of them is already bound
+    pub fn bindx_add<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<()> {
+        self.0.bindx_add(&resolve_addrs(addresses)?.1)
+    }
+
+    /// Remove local addresses from the endpoint at runtime. Fails if one of them is not bound,
+    /// if it would leave the endpoint without any address, or without an address of the same
+    /// family as the primary destination of the peer
+    pub fn bindx_remove<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<()> {
+        self.0.bindx_remove(&resolve_addrs(addresses)?.1)
+    }
+
     /// Get local socket addresses to which this socket is bound
     pub fn local_addrs(&self) -> Result<Vec<SocketAddr>> {
         self.0.local_addrs(0)
     }
 
+    /// Subscribe to the SCTP events enabled in `events`
+    #[cfg(target_os = "linux")]
+    pub fn subscribe_events(&self, events: &EventSubscription) -> Result<()> {
+        self.0.subscribe_events(events)
+    }
+
+    /// Get the SCTP events this endpoint is subscribed to
+    #[cfg(target_os = "linux")]
+    pub fn events(&self) -> Result<EventSubscription> {
+        self.0.events()
+    }
+
+    /// Convert this endpoint into a `SctpListener`. The type of a socket is fixed when it's
+    /// created, so this only succeeds if the underlying socket actually is a one-to-one
+    /// (`SOCK_STREAM`) socket, e.g. when built with `from_raw_fd`. Otherwise, it fails with
+    /// `ErrorKind::InvalidInput`, handing the endpoint back along with the error
+    #[cfg(target_os = "linux")]
+    pub fn try_into_listener(self) -> std::result::Result<SctpListener, (SctpEndpoint, Error)> {
+        match self.0.sock_type() {
+            Ok(SOCK_STREAM) => Ok(SctpListener::from_socket(self.0)),
+            Ok(_) => Err((
+                self,
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "One-to-many socket can't be used as a listener",
+                ),
+            )),
+            Err(err) => Err((self, err)),
+        }
+    }
+
+    /// Detach the association `assoc` from this endpoint into its own `SctpStream`, e.g. to
+    /// hand it off to a worker. The id of the association a message belongs to is given by
+    /// `recv_from_info`, with `SCTP_RCVINFO` enabled (see `set_recv_rcvinfo`). The stream
+    /// starts with no event subscription, and its options are independent from the ones of
+    /// this endpoint: `subscribe_events` on the stream leaves the endpoint untouched, and the
+    /// other way around
+    #[cfg(target_os = "linux")]
+    pub fn peeloff(&self, assoc: sctp_assoc_t) -> Result<SctpStream> {
+        let sock = self.0.peeloff(assoc)?;
+        sock.subscribe_events(&EventSubscription::default())?;
+        Ok(SctpStream::from_socket(sock))
+    }
+
+    /// Get a handle on the association `assoc` of this endpoint
+    #[cfg(target_os = "linux")]
+    pub fn association(&self, assoc: sctp_assoc_t) -> Association<'_> {
+        Association::new(self, assoc)
+    }
+
+    /// Get the statistics of the association `assoc`.
+    /// Shortcut for `self.association(assoc).assoc_stats()`
+    #[cfg(target_os = "linux")]
+    pub fn assoc_stats(&self, assoc: sctp_assoc_t) -> Result<AssocStats> {
+        Ok(AssocStats::from_raw(&self.0.assoc_stats(assoc)?))
+    }
+
+    /// Get the ids of the current associations of this endpoint
+    #[cfg(target_os = "linux")]
+    pub fn assoc_ids(&self) -> Result<Vec<sctp_assoc_t>> {
+        self.0.assoc_ids()
+    }
+
+    /// Gracefully shut down the associations idle since before `idle_since`, and return
+    /// how many were shut down. As the kernel doesn't track application activity,
+    /// `last_activity` gives the time of the last activity of each association, as tracked
+    /// by the application. Associations for which it returns `None` are left untouched
+    #[cfg(target_os = "linux")]
+    pub fn close_idle_assocs<F>(&self, idle_since: Instant, mut last_activity: F) -> Result<usize>
+    where
+        F: FnMut(sctp_assoc_t) -> Option<Instant>,
+    {
+        let mut closed = 0;
+        for id in self.assoc_ids()? {
+            if last_activity(id).is_some_and(|last| last < idle_since) {
+                self.association(id).shutdown()?;
+                closed += 1;
+            }
+        }
+        Ok(closed)
+    }
+
+    /// Get the live status of the association `assoc`.
+    /// Shortcut for `self.association(assoc).status()`
+    #[cfg(target_os = "linux")]
+    pub fn status(&self, assoc: sctp_assoc_t) -> Result<SctpStatus> {
+        self.0.status(assoc)
+    }
+
     /// Shuts down the read, write, or both halves of this connection
     pub fn shutdown(&self, how: Shutdown) -> Result<()> {
         self.0.shutdown(how)
@@ -287,6 +1641,58 @@ impl SctpEndpoint {
         Ok(val == 1)
     }
 
+    /// Set the initial, maximum and minimum retransmission timeouts of the association
+    /// `assoc`, in milliseconds. With `assoc` 0, set the defaults of new associations.
+    /// Fails with `ErrorKind::InvalidInput` unless `min <= initial <= max`
+    #[cfg(target_os = "linux")]
+    pub fn set_rto_info(
+        &self,
+        assoc: sctp_assoc_t,
+        initial: u32,
+        max: u32,
+        min: u32,
+    ) -> Result<()> {
+        self.0.set_rto_info(assoc, initial, max, min)
+    }
+
+    /// Get the initial, maximum and minimum retransmission timeouts of the association
+    /// `assoc`, in milliseconds. With `assoc` 0, get the defaults of new associations
+    #[cfg(target_os = "linux")]
+    pub fn get_rto_info(&self, assoc: sctp_assoc_t) -> Result<(u32, u32, u32)> {
+        self.0.rto_info(assoc)
+    }
+
+    /// Set the largest user message fragment put in a DATA chunk (`SCTP_MAXSEG`) for the
+    /// association `assoc`, in bytes. With `assoc` 0, set it for new associations.
+    /// A `size` of 0 restores the default, which follows the path MTU
+    #[cfg(target_os = "linux")]
+    pub fn set_max_seg(&self, assoc: sctp_assoc_t, size: u32) -> Result<()> {
+        self.0.set_max_seg(assoc, size)
+    }
+
+    /// Get the fragmentation point (`SCTP_MAXSEG`) of the association `assoc`, in bytes.
+    /// With `assoc` 0, get the value set for new associations, 0 meaning the default
+    #[cfg(target_os = "linux")]
+    pub fn get_max_seg(&self, assoc: sctp_assoc_t) -> Result<u32> {
+        self.0.max_seg(assoc)
+    }
+
+    /// Configure delayed SACKs of the new associations: a SACK is sent after `freq`
+    /// packets, or after `delay` if fewer packets were received. Use a zero `delay` with
+    /// `freq` 1 to acknowledge every packet immediately, or a `delay` between 1 and 500 ms
+    /// with `freq` 2 or more. Other combinations fail with `ErrorKind::InvalidInput`
+    #[cfg(target_os = "linux")]
+    pub fn set_delayed_sack(&self, delay: Duration, freq: u32) -> Result<()> {
+        self.0.set_delayed_sack(0, delay, freq)
+    }
+
+    /// Get the delayed SACK delay and frequency of the new associations. A frequency of 1
+    /// means delayed SACKs are disabled
+    #[cfg(target_os = "linux")]
+    pub fn delayed_sack(&self) -> Result<(Duration, u32)> {
+        self.0.delayed_sack(0)
+    }
+
     /// Set the socket buffer size for the direction specified by `dir`.
     /// Linux systems will double the provided size
     pub fn set_buffer_size(&self, dir: SoDirection, size: usize) -> Result<()> {
@@ -302,12 +1708,154 @@ impl SctpEndpoint {
 
     /// Set `timeout` in seconds for operation `dir` (either receive or send)
     pub fn set_timeout(&self, dir: SoDirection, timeout: i32) -> Result<()> {
-        // Workaround: Use of long instead of libc::time_t which does not compile in windows x86_64
-        let tval = libc::timeval {
-            tv_sec: timeout as libc::c_long,
-            tv_usec: 0,
-        };
-        self.0.setsockopt(SOL_SOCKET, dir.timeout_opt(), &tval)
+        self.set_timeout_dur(dir, Some(timeout_secs(timeout)?))
+    }
+
+    /// Set `timeout` for operation `dir` (either receive or send), with microsecond precision.
+    /// `None` or a zero duration clear the timeout
+    pub fn set_timeout_dur(&self, dir: SoDirection, timeout: Option<Duration>) -> Result<()> {
+        self.0.set_timeout(dir.timeout_opt(), timeout)
+    }
+
+    /// Get the timeout for operation `dir` (either receive or send). `None` means there's
+    /// no timeout
+    pub fn get_timeout(&self, dir: SoDirection) -> Result<Option<Duration>> {
+        self.0.timeout(dir.timeout_opt())
+    }
+
+    /// Enable or disable reception of ICMP-derived path errors in the socket error queue
+    /// (`IP_RECVERR` / `IPV6_RECVERR`)
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_err(&self, on: bool) -> Result<()> {
+        self.0.set_recv_err(on)
+    }
+
+    /// Read the next extended error from the socket error queue without blocking.
+    /// Returns `None` if there's no pending error
+    #[cfg(target_os = "linux")]
+    pub fn recv_error(&self) -> Result<Option<SctpExtError>> {
+        self.0.recv_error()
+    }
+
+    /// Set how many times INIT is retransmitted before giving up on a new association, so
+    /// that sending to an unreachable peer fails faster. Only affects associations set up
+    /// after this call
+    #[cfg(target_os = "linux")]
+    pub fn set_max_init_attempts(&self, attempts: u16) -> Result<()> {
+        self.0.set_max_init_attempts(attempts)
+    }
+
+    /// Move the endpoint into or out of non-blocking mode. In non-blocking mode, receive
+    /// operations return an `ErrorKind::WouldBlock` error instead of blocking when there's no
+    /// data, and send operations when the send buffer is full
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Automatically close associations idle for more than `seconds` (`SCTP_AUTOCLOSE`).
+    /// 0 disables autoclose
+    pub fn set_autoclose(&self, seconds: u32) -> Result<()> {
+        self.0.set_autoclose(seconds)
+    }
+
+    /// Get the idle time in seconds after which associations are automatically closed.
+    /// 0 means autoclose is disabled. The kernel doesn't expose how much idle time is left
+    /// to each association before it gets closed
+    pub fn autoclose(&self) -> Result<u32> {
+        self.0.autoclose()
+    }
+
+    /// Set the HMAC algorithms, by order of preference, used to authenticate the state
+    /// cookies of incoming associations (`SCTP_HMAC_IDENT`). Algorithms are identified by the
+    /// `SCTP_AUTH_HMAC_ID_*` constants, and at least one of them must be supported by the kernel
+    #[cfg(target_os = "linux")]
+    pub fn set_hmac_ident(&self, algos: &[u16]) -> Result<()> {
+        self.0.set_hmac_ident(algos)
+    }
+
+    /// Get the HMAC algorithms used to authenticate state cookies, by order of preference
+    #[cfg(target_os = "linux")]
+    pub fn hmac_ident(&self) -> Result<Vec<u16>> {
+        self.0.hmac_ident()
+    }
+
+    /// Set the partial reliability policy applied by default to sent messages
+    /// (`SCTP_DEFAULT_PRINFO`), if the peer supports PR-SCTP. The kernel only applies it to
+    /// messages sent without explicit send parameters, while every send method of this
+    /// endpoint passes its own with each message and ignores this default: use `send_to_pr`
+    /// for a per-message policy instead
+    #[cfg(target_os = "linux")]
+    pub fn set_default_pr(&self, policy: PrPolicy) -> Result<()> {
+        self.0.set_default_prinfo(0, policy)
+    }
+
+    /// Get the partial reliability policy applied by default to sent messages
+    #[cfg(target_os = "linux")]
+    pub fn default_pr(&self) -> Result<PrPolicy> {
+        self.0.default_prinfo(0)
+    }
+
+    /// Enable or disable kernel socket debug tracing (`SO_DEBUG`), to be used together
+    /// with tools like `ss`. Requires privileges (`CAP_NET_ADMIN` on Linux), and
+    /// fails with `ErrorKind::PermissionDenied` when not permitted
+    pub fn set_debug(&self, on: bool) -> Result<()> {
+        self.0.set_debug(on)
+    }
+
+    /// Choose whether sends go through `sendmsg(2)` with an `SCTP_SNDINFO` control message,
+    /// which is the default on Linux, or through the deprecated `sctp_sendmsg` function.
+    /// Sends with a lifetime always use `sctp_sendmsg`, as `SCTP_SNDINFO` can't carry it
+    #[cfg(target_os = "linux")]
+    pub fn prefer_modern_api(&self, modern: bool) {
+        self.0.prefer_modern_api(modern)
+    }
+
+    /// Wait for the next SCTP notification, for at most `timeout` if not `None`, returning
+    /// `None` on timeout. Notifications must have been subscribed to with `EventSubscription`.
+    /// Data messages are never consumed, so this is meant for sockets whose data is handled
+    /// elsewhere (e.g. on peeled off associations), and must be the only consumer of the
+    /// receive queue. When a data message is at its head, it is left there and this fails
+    /// with `ErrorKind::InvalidData`. At end of file, this fails with
+    /// `ErrorKind::UnexpectedEof`. To receive notifications while data is handled on the
+    /// same socket, use `spawn_notification_handler`, which delivers both
+    #[cfg(target_os = "linux")]
+    pub fn recv_notification(&self, timeout: Option<Duration>) -> Result<Option<SctpNotification>> {
+        self.0.recv_notification(timeout)
+    }
+
+    /// Set or unset the close-on-exec flag, telling whether the socket is closed in child
+    /// processes on `exec`. Sockets created by this crate have it set
+    #[cfg(target_os = "linux")]
+    pub fn set_cloexec(&self, cloexec: bool) -> Result<()> {
+        self.0.set_cloexec(cloexec)
+    }
+
+    /// Check if the socket is in non-blocking mode
+    #[cfg(target_os = "linux")]
+    pub fn is_nonblocking(&self) -> Result<bool> {
+        self.0.is_nonblocking()
+    }
+
+    /// Spawn a thread handling the SCTP notifications of this socket, and return its join
+    /// handle along with the channel they are delivered into. The socket is subscribed to
+    /// every notification. The data messages read by the thread are delivered into the
+    /// channel too, so the other threads can keep receiving on the socket without losing
+    /// any. The thread runs on a clone of the socket and stops once the channel is dropped,
+    /// at end of file, or on a receive error
+    #[cfg(target_os = "linux")]
+    pub fn spawn_notification_handler(
+        &self,
+    ) -> Result<(JoinHandle<Result<()>>, Receiver<IncomingMessage>)> {
+        notifications::spawn_handler(self.0.try_clone()?)
+    }
+
+    /// Get an asynchronous stream of the SCTP notifications of this socket, driven by the
+    /// Tokio reactor. The socket is subscribed to every notification, and the data messages
+    /// read by the stream are yielded too. The stream runs on a clone of the socket, and
+    /// must be created from within a Tokio runtime
+    #[cfg(all(target_os = "linux", feature = "tokio"))]
+    pub fn notification_stream(&self) -> Result<NotificationStream> {
+        NotificationStream::new(self.0.try_clone()?)
     }
 
     /// Try to clone this socket
@@ -351,6 +1899,9 @@ impl<'a> std::iter::Iterator for Incoming<'a> {
     type Item = Result<SctpStream>;
 
     fn next(&mut self) -> Option<Result<SctpStream>> {
+        if !self.0.is_accepting() {
+            return None;
+        }
         match self.0.accept() {
             Ok((stream, _)) => Some(Ok(stream)),
             Err(e) => Some(Err(e)),
@@ -361,50 +1912,236 @@ impl<'a> std::iter::Iterator for Incoming<'a> {
 /// SCTP listener which behaves like a `TcpListener`.
 /// A SCTP listener is used to wait for and accept one-to-one SCTP connections.
 /// An accepted connection is represented by `SctpStream`.
-pub struct SctpListener(SctpSocket);
+pub struct SctpListener(SctpSocket, Arc<ListenerState>);
+
+/// State shared by a listener and its clones
+#[derive(Default)]
+struct ListenerState {
+    /// Set by `shutdown_accepts`
+    shutdown: AtomicBool,
+    /// Options applied to accepted streams, set by `set_accept_template`
+    #[cfg(target_os = "linux")]
+    accept_template: Mutex<Option<SctpOptions>>,
+}
 
 impl SctpListener {
+    fn from_socket(sock: SctpSocket) -> SctpListener {
+        SctpListener(sock, Arc::default())
+    }
+
+    fn check_accepting(&self) -> Result<()> {
+        if self.1.shutdown.load(Ordering::Acquire) {
+            return Err(Error::new(
+                ErrorKind::ConnectionAborted,
+                "Listener no longer accepts connections",
+            ));
+        }
+        Ok(())
+    }
+
     /// Create a listener bound to a single address
     pub fn bind<A: ToSocketAddrs>(address: A) -> Result<SctpListener> {
+        SctpListener::bind_with_backlog(address, -1)
+    }
+
+    /// Create a listener bound to a single address, with at most `backlog` pending
+    /// connections. A negative value uses the system maximum
+    pub fn bind_with_backlog<A: ToSocketAddrs>(address: A, backlog: i32) -> Result<SctpListener> {
         let raw_addr = SocketAddr::from_addr(&address)?;
-        let sock = SctpSocket::new(raw_addr.family(), SOCK_STREAM)?;
+        let sock = new_socket(raw_addr.family(), SOCK_STREAM)?;
         sock.bind(raw_addr)?;
-        sock.listen(-1)?;
-        Ok(SctpListener(sock))
+        sock.listen(backlog)?;
+        Ok(SctpListener::from_socket(sock))
     }
 
-    /// Create a listener bound to multiple addresses. Requires at least one address
+    /// Create a listener bound to multiple addresses. Requires at least one address.
+    /// On hosts without IPv6 support, IPv6 addresses are ignored
     pub fn bindx<A: ToSocketAddrs>(addresses: &[A]) -> Result<SctpListener> {
-        if addresses.is_empty() {
-            return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
-        }
-        let mut vec = Vec::with_capacity(addresses.len());
-        let mut family = AF_INET;
-        for address in addresses {
-            let a = SocketAddr::from_addr(address)?;
-            if a.family() == AF_INET6 {
-                family = AF_INET6;
-            }
-            vec.push(a);
-        }
+        SctpListener::bindx_with_backlog(addresses, -1)
+    }
 
-        let sock = SctpSocket::new(family, SOCK_STREAM)?;
+    /// Create a listener bound to multiple addresses, with at most `backlog` pending
+    /// connections. A negative value uses the system maximum. Requires at least one address
+    pub fn bindx_with_backlog<A: ToSocketAddrs>(
+        addresses: &[A],
+        backlog: i32,
+    ) -> Result<SctpListener> {
+        let (family, mut vec) = resolve_addrs(addresses)?;
+        let sock = new_socket_for(family, &mut vec, SOCK_STREAM)?;
         sock.bindx(&vec, BindOp::AddAddr)?;
-        sock.listen(-1)?;
-        Ok(SctpListener(sock))
+        sock.listen(backlog)?;
+        Ok(SctpListener::from_socket(sock))
+    }
+
+    /// Create a listener bound to all the IPv4 and IPv6 addresses of the network interface
+    /// `ifname` (e.g. `eth0`), on port `port`. Addresses are looked up once, when binding
+    #[cfg(target_os = "linux")]
+    pub fn bind_interface(ifname: &str, port: u16) -> Result<SctpListener> {
+        SctpListener::bindx(&interface_addrs(ifname, port)?)
     }
 
-    /// Accept a new connection
+    /// Accept a new connection.
+    /// The accepted stream inherits the listener's socket options (buffer sizes, nodelay,
+    /// timeouts, ...). Use `accept_configure` to override them on each new stream
     pub fn accept(&self) -> Result<(SctpStream, SocketAddr)> {
-        let (sock, addr) = self.0.accept()?;
-        Ok((SctpStream(sock), addr))
+        self.check_accepting()?;
+        let res = self.0.accept();
+        // Connections accepted while shutting accepts down are closed, and the threads
+        // woken up by `shutdown_accepts` get the same error as the later calls
+        self.check_accepting()?;
+        let (sock, addr) = res?;
+        #[cfg(target_os = "linux")]
+        if let Some(ref template) = *self.1.accept_template.lock().unwrap() {
+            template.config.apply(&sock)?;
+        }
+        Ok((SctpStream::from_socket(sock), addr))
+    }
+
+    /// Set the options explicitly applied by `accept` to each accepted stream, instead of
+    /// relying on the platform specific inheritance of the listener's options. If applying
+    /// them fails, the new stream is closed and `accept` returns the error.
+    /// The template is shared with the clones obtained from `try_clone`. `None` removes it
+    #[cfg(target_os = "linux")]
+    pub fn set_accept_template(&self, template: Option<SctpOptions>) {
+        *self.1.accept_template.lock().unwrap() = template;
+    }
+
+    /// Accept a new connection and run the configuration closure `f` on it before
+    /// handing it over. If `f` fails, the new stream is closed and the error is returned
+    pub fn accept_configure<F>(&self, f: F) -> Result<(SctpStream, SocketAddr)>
+    where
+        F: FnOnce(&SctpStream) -> Result<()>,
+    {
+        let (stream, addr) = self.accept()?;
+        f(&stream)?;
+        Ok((stream, addr))
+    }
+
+    /// Accept a new connection, or return an `ErrorKind::Interrupted` error if `token`
+    /// is triggered while waiting. Use `Cancelled::is` to tell it apart from other errors
+    #[cfg(target_os = "linux")]
+    pub fn accept_cancellable(&self, token: &ShutdownToken) -> Result<(SctpStream, SocketAddr)> {
+        self.0.wait_readable(token)?;
+        self.accept()
+    }
+
+    /// Accept a new connection if one is pending, without blocking.
+    /// Returns `None` if there's no pending connection. The listener must have been set
+    /// in non-blocking mode with `set_nonblocking(true)`, otherwise this fails with
+    /// `ErrorKind::InvalidInput` instead of blocking like `accept`
+    #[cfg(target_os = "linux")]
+    pub fn poll_accept(&self) -> Result<Option<(SctpStream, SocketAddr)>> {
+        if !self.0.is_nonblocking()? {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "poll_accept requires a non-blocking listener",
+            ));
+        }
+        match self.accept() {
+            Ok(res) => Ok(Some(res)),
+            Err(ref err) if err.kind() == ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Move the listener into or out of non-blocking mode. In non-blocking mode,
+    /// `accept` returns an `ErrorKind::WouldBlock` error when there's no pending connection
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Capture the options of this listener, to create other listeners configured
+    /// the same way, e.g. on other ports
+    #[cfg(target_os = "linux")]
+    pub fn config(&self) -> Result<ListenerConfig> {
+        ListenerConfig::capture(self)
+    }
+
+    /// Convert this listener into a `SctpEndpoint`. The type of a socket is fixed when it's
+    /// created, so this only succeeds if the underlying socket actually is a one-to-many
+    /// (`SOCK_SEQPACKET`) socket, e.g. when built with `from_raw_fd`. Otherwise, it fails
+    /// with `ErrorKind::InvalidInput`, handing the listener back along with the error
+    #[cfg(target_os = "linux")]
+    pub fn try_into_endpoint(self) -> std::result::Result<SctpEndpoint, (SctpListener, Error)> {
+        match self.0.sock_type() {
+            Ok(SOCK_SEQPACKET) => Ok(SctpEndpoint(self.0)),
+            Ok(_) => Err((
+                self,
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "One-to-one socket can't be used as an endpoint",
+                ),
+            )),
+            Err(err) => Err((self, err)),
+        }
+    }
+
+    /// Stop accepting new connections, without closing the listening socket, so that a
+    /// server can drain its in-flight connections before the final close. Afterwards,
+    /// `accept` fails with `ErrorKind::ConnectionAborted` and `incoming` ends.
+    ///
+    /// On Linux, the socket stops listening (`listen(0)`), so the kernel no longer completes
+    /// the handshakes of new peers, and the threads blocked in `accept` are woken up. As this
+    /// applies to the socket itself, every listener sharing it stops accepting: the clones
+    /// obtained from `try_clone` fail with `ErrorKind::ConnectionAborted` too, while the
+    /// listeners built from the same file descriptor with `from_raw_fd` fail with the
+    /// `EINVAL` returned by the kernel. The connections established but not accepted yet
+    /// are closed along with the listener
+    pub fn shutdown_accepts(&self) -> Result<()> {
+        self.1.shutdown.store(true, Ordering::Release);
+        #[cfg(target_os = "linux")]
+        {
+            self.0.listen(0)?;
+            // Wake up the threads blocked in accept. As the socket no longer listens, the
+            // kernel reports it as not connected
+            match self.0.shutdown(Shutdown::Read) {
+                Err(ref err) if err.raw_os_error() == Some(libc::ENOTCONN) => {}
+                res => res?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if `shutdown_accepts` was called on this listener or one of its clones
+    pub fn is_accepting(&self) -> bool {
+        !self.1.shutdown.load(Ordering::Acquire)
+    }
+
+    /// Set or unset the close-on-exec flag, telling whether the socket is closed in child
+    /// processes on `exec`. Sockets created by this crate have it set
+    #[cfg(target_os = "linux")]
+    pub fn set_cloexec(&self, cloexec: bool) -> Result<()> {
+        self.0.set_cloexec(cloexec)
+    }
+
+    /// Check if the listener is in non-blocking mode
+    #[cfg(target_os = "linux")]
+    pub fn is_nonblocking(&self) -> Result<bool> {
+        self.0.is_nonblocking()
     }
 
     /// Iterate over new connections
-    pub fn incoming(&self) -> Incoming {
+    pub fn incoming(&self) -> Incoming<'_> {
         Incoming(self)
     }
 
+    /// Check if the listener is bound to `address`. A port of 0 matches any port
+    pub fn is_bound_to<A: ToSocketAddrs>(&self, address: A) -> Result<bool> {
+        self.0.is_bound_to(SocketAddr::from_addr(&address)?)
+    }
+
+    /// Add local addresses to the listener at runtime. Fails if one of them is already bound
+    pub fn bindx_add<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<()> {
+        self.0.bindx_add(&resolve_addrs(addresses)?.1)
+    }
+
+    /// Remove local addresses from the listener at runtime. Fails if one of them is not bound,
+    /// if it would leave the listener without any address, or without an address of the same
+    /// family as the primary destination of the peer
+    pub fn bindx_remove<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<()> {
+        self.0.bindx_remove(&resolve_addrs(addresses)?.1)
+    }
+
     /// Get the listener local addresses
     pub fn local_addrs(&self) -> Result<Vec<SocketAddr>> {
         self.0.local_addrs(0)
@@ -412,17 +2149,44 @@ impl SctpListener {
 
     /// Set `timeout` in seconds on accept
     pub fn set_timeout(&self, timeout: i32) -> Result<()> {
-        // Workaround: Use of long instead of libc::time_t which does not compile in windows x86_64
-        let tval = libc::timeval {
-            tv_sec: timeout as libc::c_long,
-            tv_usec: 0,
-        };
-        self.0.setsockopt(SOL_SOCKET, SO_RCVTIMEO, &tval)
+        self.set_timeout_dur(Some(timeout_secs(timeout)?))
+    }
+
+    /// Set `timeout` on accept operations, with microsecond precision.
+    /// `None` or a zero duration clear the timeout
+    pub fn set_timeout_dur(&self, timeout: Option<Duration>) -> Result<()> {
+        self.0.set_timeout(SO_RCVTIMEO, timeout)
+    }
+
+    /// Get the timeout on accept operations. `None` means there's no timeout
+    pub fn get_timeout(&self) -> Result<Option<Duration>> {
+        self.0.timeout(SO_RCVTIMEO)
+    }
+
+    /// Set the HMAC algorithms, by order of preference, used to authenticate the state
+    /// cookies of incoming associations (`SCTP_HMAC_IDENT`). Algorithms are identified by the
+    /// `SCTP_AUTH_HMAC_ID_*` constants, and at least one of them must be supported by the kernel
+    #[cfg(target_os = "linux")]
+    pub fn set_hmac_ident(&self, algos: &[u16]) -> Result<()> {
+        self.0.set_hmac_ident(algos)
+    }
+
+    /// Get the HMAC algorithms used to authenticate state cookies, by order of preference
+    #[cfg(target_os = "linux")]
+    pub fn hmac_ident(&self) -> Result<Vec<u16>> {
+        self.0.hmac_ident()
+    }
+
+    /// Enable or disable kernel socket debug tracing (`SO_DEBUG`), to be used together
+    /// with tools like `ss`. Requires privileges (`CAP_NET_ADMIN` on Linux), and
+    /// fails with `ErrorKind::PermissionDenied` when not permitted
+    pub fn set_debug(&self, on: bool) -> Result<()> {
+        self.0.set_debug(on)
     }
 
     /// Try to clone this listener
     pub fn try_clone(&self) -> Result<SctpListener> {
-        Ok(SctpListener(self.0.try_clone()?))
+        Ok(SctpListener(self.0.try_clone()?, self.1.clone()))
     }
 }
 
@@ -436,7 +2200,7 @@ impl AsRawHandle for SctpListener {
 #[cfg(target_os = "windows")]
 impl FromRawHandle for SctpListener {
     unsafe fn from_raw_handle(hdl: RawHandle) -> SctpListener {
-        SctpListener(SctpSocket::from_raw_handle(hdl))
+        SctpListener::from_socket(SctpSocket::from_raw_handle(hdl))
     }
 }
 
@@ -450,6 +2214,6 @@ impl AsRawFd for SctpListener {
 #[cfg(target_os = "linux")]
 impl FromRawFd for SctpListener {
     unsafe fn from_raw_fd(fd: RawFd) -> SctpListener {
-        SctpListener(SctpSocket::from_raw_fd(fd))
+        SctpListener::from_socket(SctpSocket::from_raw_fd(fd))
     }
 }
@@ -9,11 +9,20 @@ macro_rules! syscall {
 	($fn: ident ( $($arg: expr),* $(,)* ) ) => {{
         #[allow(clippy::macro_metavars_in_unsafe)]
 		let res = unsafe { libc::$fn($($arg, )*) };
-		if res == -1 {
+		let ret = if res == -1 {
 			Err(std::io::Error::last_os_error())
 		} else {
 			Ok(res)
-		}
+		};
+		#[cfg(feature = "trace")]
+		#[allow(clippy::unnecessary_cast)]
+		$crate::trace::emit(
+			stringify!($fn),
+			stringify!($($arg),*),
+			res as isize,
+			ret.as_ref().err(),
+		);
+		ret
 	}};
 }
 
@@ -23,11 +32,20 @@ macro_rules! sctp_syscall {
 	($fn: ident ( $($arg: expr),* $(,)* ) ) => {{
         #[allow(clippy::macro_metavars_in_unsafe)]
 		let res = unsafe { sctp_sys::$fn($($arg, )*) };
-		if res == -1 {
+		let ret = if res == -1 {
 			Err(std::io::Error::last_os_error())
 		} else {
 			Ok(res)
-		}
+		};
+		#[cfg(feature = "trace")]
+		#[allow(clippy::unnecessary_cast)]
+		$crate::trace::emit(
+			stringify!($fn),
+			stringify!($($arg),*),
+			res as isize,
+			ret.as_ref().err(),
+		);
+		ret
 	}};
 }
 
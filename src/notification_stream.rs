@@ -0,0 +1,79 @@
+//! Asynchronous stream of SCTP notifications, driven by the Tokio reactor
+
+use std::future::poll_fn;
+use std::io::Result;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::unix::AsyncFd;
+
+use crate::notifications;
+use crate::recv::{IncomingMessage, MessageAssembler};
+use crate::sctpsock::{SctpSocket, CONTROL_LEN};
+
+/// Stream of the SCTP notifications received on a socket, obtained with
+/// `SctpStream::notification_stream` or `SctpEndpoint::notification_stream`.
+/// The data messages it reads from the receive queue shared with notifications are yielded
+/// as `IncomingMessage::Data`. The stream ends when the socket reaches end of file
+pub struct NotificationStream {
+    sock: AsyncFd<SctpSocket>,
+    buf: Box<[u8]>,
+    assembler: MessageAssembler,
+    eof: bool,
+}
+
+impl NotificationStream {
+    /// Subscribe `sock` to every notification and register it in the Tokio reactor.
+    /// Must be called from within a Tokio runtime
+    pub(crate) fn new(sock: SctpSocket) -> Result<NotificationStream> {
+        notifications::subscribe_all(&sock)?;
+        Ok(NotificationStream {
+            sock: AsyncFd::new(sock)?,
+            buf: vec![0u8; 4096].into_boxed_slice(),
+            assembler: MessageAssembler::default(),
+            eof: false,
+        })
+    }
+
+    /// Wait for the next notification or data message. Returns `None` once the socket
+    /// reached end of file
+    pub async fn next(&mut self) -> Option<Result<IncomingMessage>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl Stream for NotificationStream {
+    type Item = Result<IncomingMessage>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.eof {
+            let mut guard = ready!(this.sock.poll_read_ready(cx))?;
+            // The socket may be shared with blocking users, so it is never put in
+            // non-blocking mode: each receive is made non-blocking instead
+            let received = match guard.try_io(|sock| {
+                sock.get_ref()
+                    .recv_full_flags(&mut this.buf, CONTROL_LEN, libc::MSG_DONTWAIT)
+            }) {
+                Ok(Ok(received)) => received,
+                Ok(Err(err)) => {
+                    // The pieces received so far don't belong to the next message
+                    this.assembler.reset();
+                    return Poll::Ready(Some(Err(err)));
+                }
+                Err(_would_block) => continue,
+            };
+            if !received.is_notification && received.bytes == 0 {
+                this.eof = true;
+                continue;
+            }
+            match this.assembler.push(&this.buf, received) {
+                Ok(Some(incoming)) => return Poll::Ready(Some(Ok(incoming))),
+                Ok(None) => continue,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+        Poll::Ready(None)
+    }
+}
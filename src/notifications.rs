@@ -0,0 +1,405 @@
+//! SCTP events subscription and notifications
+
+use std::io::{Error, ErrorKind, Result};
+use std::mem::size_of;
+use std::net::SocketAddr;
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, JoinHandle};
+
+use sctp_sys::sctp_assoc_t;
+
+use crate::ffi;
+use crate::mio_unix::to_socket_addr;
+use crate::recv::{IncomingMessage, MessageAssembler};
+use crate::sctpsock::SctpSocket;
+
+/// Set of SCTP events to subscribe to. Each enabled event is delivered as a notification
+/// on the receive path of the socket
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventSubscription {
+    /// Receive `sctp_sndrcvinfo` ancillary data with each message
+    pub data_io: bool,
+    /// Association changes (`SCTP_ASSOC_CHANGE`)
+    pub association: bool,
+    /// Peer address changes (`SCTP_PEER_ADDR_CHANGE`)
+    pub address: bool,
+    /// Send failures (`SCTP_SEND_FAILED`)
+    pub send_failure: bool,
+    /// Operational errors reported by the peer (`SCTP_REMOTE_ERROR`)
+    pub peer_error: bool,
+    /// Peer shutdown (`SCTP_SHUTDOWN_EVENT`)
+    pub shutdown: bool,
+    /// Partial delivery events (`SCTP_PARTIAL_DELIVERY_EVENT`)
+    pub partial_delivery: bool,
+    /// Adaptation layer indications (`SCTP_ADAPTATION_INDICATION`)
+    pub adaptation_layer: bool,
+    /// Authentication events (`SCTP_AUTHENTICATION_EVENT`)
+    pub authentication: bool,
+    /// Sender dry events (`SCTP_SENDER_DRY_EVENT`)
+    pub sender_dry: bool,
+    /// Stream reset events (`SCTP_STREAM_RESET_EVENT`)
+    pub stream_reset: bool,
+}
+
+impl EventSubscription {
+    /// Subscription with every event enabled
+    pub fn all() -> EventSubscription {
+        EventSubscription {
+            data_io: true,
+            association: true,
+            address: true,
+            send_failure: true,
+            peer_error: true,
+            shutdown: true,
+            partial_delivery: true,
+            adaptation_layer: true,
+            authentication: true,
+            sender_dry: true,
+            stream_reset: true,
+        }
+    }
+
+    pub(crate) fn to_raw(self) -> ffi::sctp_event_subscribe {
+        ffi::sctp_event_subscribe {
+            sctp_data_io_event: self.data_io as u8,
+            sctp_association_event: self.association as u8,
+            sctp_address_event: self.address as u8,
+            sctp_send_failure_event: self.send_failure as u8,
+            sctp_peer_error_event: self.peer_error as u8,
+            sctp_shutdown_event: self.shutdown as u8,
+            sctp_partial_delivery_event: self.partial_delivery as u8,
+            sctp_adaptation_layer_event: self.adaptation_layer as u8,
+            sctp_authentication_event: self.authentication as u8,
+            sctp_sender_dry_event: self.sender_dry as u8,
+            sctp_stream_reset_event: self.stream_reset as u8,
+        }
+    }
+
+    pub(crate) fn from_raw(raw: &ffi::sctp_event_subscribe) -> EventSubscription {
+        EventSubscription {
+            data_io: raw.sctp_data_io_event != 0,
+            association: raw.sctp_association_event != 0,
+            address: raw.sctp_address_event != 0,
+            send_failure: raw.sctp_send_failure_event != 0,
+            peer_error: raw.sctp_peer_error_event != 0,
+            shutdown: raw.sctp_shutdown_event != 0,
+            partial_delivery: raw.sctp_partial_delivery_event != 0,
+            adaptation_layer: raw.sctp_adaptation_layer_event != 0,
+            authentication: raw.sctp_authentication_event != 0,
+            sender_dry: raw.sctp_sender_dry_event != 0,
+            stream_reset: raw.sctp_stream_reset_event != 0,
+        }
+    }
+}
+
+/// Change of an association, as reported by `SctpNotification::AssocChange`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssocChangeState {
+    /// A new association is ready to send and receive data
+    CommUp,
+    /// The association failed, e.g. it was aborted or the peer is unreachable
+    CommLost,
+    /// The peer restarted
+    Restart,
+    /// The association was gracefully shut down
+    ShutdownComplete,
+    /// The association could not be established
+    CantStartAssoc,
+    /// State value unknown to this crate
+    Unknown(u16),
+}
+
+impl AssocChangeState {
+    /// Convert a raw `sac_state` value
+    pub fn from_raw(state: u16) -> AssocChangeState {
+        match state {
+            ffi::SCTP_COMM_UP => AssocChangeState::CommUp,
+            ffi::SCTP_COMM_LOST => AssocChangeState::CommLost,
+            ffi::SCTP_RESTART => AssocChangeState::Restart,
+            ffi::SCTP_SHUTDOWN_COMP => AssocChangeState::ShutdownComplete,
+            ffi::SCTP_CANT_STR_ASSOC => AssocChangeState::CantStartAssoc,
+            other => AssocChangeState::Unknown(other),
+        }
+    }
+}
+
+/// State of a peer address, as reported by `SctpNotification::PeerAddrChange`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerAddrState {
+    /// The address is now reachable
+    Available,
+    /// The address can no longer be reached. Traffic fails over to another address
+    Unreachable,
+    /// The address was removed from the association
+    Removed,
+    /// The address was added to the association
+    Added,
+    /// The address is now the primary destination
+    MadePrimary,
+    /// The address was confirmed as valid
+    Confirmed,
+    /// The address is potentially failed
+    PotentiallyFailed,
+    /// State value unknown to this crate
+    Unknown(i32),
+}
+
+impl PeerAddrState {
+    /// Convert a raw `spc_state` value
+    pub fn from_raw(state: i32) -> PeerAddrState {
+        match state {
+            ffi::SCTP_ADDR_AVAILABLE => PeerAddrState::Available,
+            ffi::SCTP_ADDR_UNREACHABLE => PeerAddrState::Unreachable,
+            ffi::SCTP_ADDR_REMOVED => PeerAddrState::Removed,
+            ffi::SCTP_ADDR_ADDED => PeerAddrState::Added,
+            ffi::SCTP_ADDR_MADE_PRIM => PeerAddrState::MadePrimary,
+            ffi::SCTP_ADDR_CONFIRMED => PeerAddrState::Confirmed,
+            ffi::SCTP_ADDR_POTENTIALLY_FAILED => PeerAddrState::PotentiallyFailed,
+            other => PeerAddrState::Unknown(other),
+        }
+    }
+}
+
+/// A notification received from the SCTP stack, when subscribed with `EventSubscription`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SctpNotification {
+    /// An association was established, lost, restarted or shut down
+    AssocChange {
+        /// Association the change applies to
+        assoc_id: sctp_assoc_t,
+        /// What happened to the association
+        state: AssocChangeState,
+        /// Error code giving more details about the change
+        error: u16,
+        /// Number of outbound streams of the association
+        outbound_streams: u16,
+        /// Number of inbound streams of the association
+        inbound_streams: u16,
+        /// Raw data following the fixed notification: the extensions supported by the peer on
+        /// `CommUp` and `Restart`, the ABORT chunk which caused `CommLost` or `CantStartAssoc`
+        info: Vec<u8>,
+    },
+    /// The partial delivery of a message was aborted (e.g. the association was lost in the
+    /// middle of a message). The data already delivered for this message must be discarded
+    PartialDeliveryAborted {
+        /// Association the aborted message belonged to
+        assoc_id: sctp_assoc_t,
+    },
+    /// The state of one of the peer addresses changed, e.g. on path failover
+    PeerAddrChange {
+        /// Association the address belongs to
+        assoc_id: sctp_assoc_t,
+        /// Peer address affected by the change
+        addr: SocketAddr,
+        /// New state of the address
+        state: PeerAddrState,
+        /// Error code giving more details about the change
+        error: i32,
+    },
+    /// The peer reported an operational error with an ERROR chunk
+    RemoteError {
+        /// Association the error was reported on
+        assoc_id: sctp_assoc_t,
+        /// Cause code of the error
+        error: u16,
+        /// Raw error causes carried by the ERROR chunk
+        data: Vec<u8>,
+    },
+    /// A message could not be delivered
+    SendFailed {
+        /// Association the message was sent on
+        assoc_id: sctp_assoc_t,
+        /// Error code giving more details about the failure
+        error: u32,
+        /// Whether the message was transmitted at least once, without being acknowledged
+        sent: bool,
+        /// Stream the message was sent on
+        stream: u16,
+        /// Payload protocol identifier of the message, in host byte order
+        ppid: u32,
+        /// Undelivered payload
+        data: Vec<u8>,
+    },
+    /// The peer sent a SHUTDOWN: no more data can be sent on the association
+    Shutdown {
+        /// Association being shut down
+        assoc_id: sctp_assoc_t,
+    },
+    /// The peer sent an adaptation layer indication
+    AdaptationIndication {
+        /// Association the indication was received on
+        assoc_id: sctp_assoc_t,
+        /// Adaptation layer indication of the peer
+        indication: u32,
+    },
+    /// All the data sent on the association was acknowledged
+    SenderDry {
+        /// Association with no more outstanding data
+        assoc_id: sctp_assoc_t,
+    },
+    /// Streams of the association were reset, or a reset request was refused
+    StreamReset {
+        /// Association the streams belong to
+        assoc_id: sctp_assoc_t,
+        /// Whether inbound streams were reset
+        incoming: bool,
+        /// Whether outbound streams were reset
+        outgoing: bool,
+        /// Whether the peer denied the request
+        denied: bool,
+        /// Whether the request failed
+        failed: bool,
+        /// Streams affected. Empty when all the streams are
+        streams: Vec<u16>,
+    },
+    /// A notification not decoded by this crate, as raw bytes
+    Raw(Vec<u8>),
+}
+
+/// Read a `T` from the beginning of `buf`, failing if `buf` is too short
+fn read_struct<T: Copy>(buf: &[u8]) -> Result<T> {
+    if buf.len() < size_of::<T>() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Truncated SCTP notification",
+        ));
+    }
+    Ok(unsafe { std::ptr::read_unaligned(buf.as_ptr() as *const T) })
+}
+
+/// Get the variable data following the fixed part `T` of a notification, bounded by the
+/// `length` it reports
+fn trailing_data<T>(buf: &[u8], length: u32) -> &[u8] {
+    let end = std::cmp::min(length as usize, buf.len());
+    buf.get(size_of::<T>()..end).unwrap_or_default()
+}
+
+impl SctpNotification {
+    /// Decode a notification from a buffer received with the `MSG_NOTIFICATION` flag set
+    pub fn parse(buf: &[u8]) -> Result<SctpNotification> {
+        let header: ffi::sctp_notification_header = read_struct(buf)?;
+        match header.sn_type {
+            ffi::SCTP_ASSOC_CHANGE => {
+                let ev: ffi::sctp_assoc_change = read_struct(buf)?;
+                let info = trailing_data::<ffi::sctp_assoc_change>(buf, ev.sac_length);
+                Ok(SctpNotification::AssocChange {
+                    assoc_id: ev.sac_assoc_id,
+                    state: AssocChangeState::from_raw(ev.sac_state),
+                    error: ev.sac_error,
+                    outbound_streams: ev.sac_outbound_streams,
+                    inbound_streams: ev.sac_inbound_streams,
+                    info: info.to_vec(),
+                })
+            }
+            ffi::SCTP_PARTIAL_DELIVERY_EVENT => {
+                let ev: ffi::sctp_pdapi_event = read_struct(buf)?;
+                match ev.pdapi_indication {
+                    ffi::SCTP_PARTIAL_DELIVERY_ABORTED => {
+                        Ok(SctpNotification::PartialDeliveryAborted {
+                            assoc_id: ev.pdapi_assoc_id,
+                        })
+                    }
+                    _ => Ok(SctpNotification::Raw(buf.to_vec())),
+                }
+            }
+            ffi::SCTP_PEER_ADDR_CHANGE => {
+                let ev: ffi::sctp_paddr_change = read_struct(buf)?;
+                let aaddr = ev.spc_aaddr;
+                Ok(SctpNotification::PeerAddrChange {
+                    assoc_id: ev.spc_assoc_id,
+                    addr: unsafe { to_socket_addr(&aaddr) }?,
+                    state: PeerAddrState::from_raw(ev.spc_state),
+                    error: ev.spc_error,
+                })
+            }
+            ffi::SCTP_REMOTE_ERROR => {
+                let ev: ffi::sctp_remote_error = read_struct(buf)?;
+                Ok(SctpNotification::RemoteError {
+                    assoc_id: ev.sre_assoc_id,
+                    error: u16::from_be(ev.sre_error),
+                    data: trailing_data::<ffi::sctp_remote_error>(buf, ev.sre_length).to_vec(),
+                })
+            }
+            ffi::SCTP_SEND_FAILED => {
+                let ev: ffi::sctp_send_failed = read_struct(buf)?;
+                Ok(SctpNotification::SendFailed {
+                    assoc_id: ev.ssf_assoc_id,
+                    error: ev.ssf_error,
+                    sent: ev.ssf_flags == ffi::SCTP_DATA_SENT,
+                    stream: ev.ssf_info.sinfo_stream,
+                    ppid: u32::from_be(ev.ssf_info.sinfo_ppid),
+                    data: trailing_data::<ffi::sctp_send_failed>(buf, ev.ssf_length).to_vec(),
+                })
+            }
+            ffi::SCTP_SHUTDOWN_EVENT => {
+                let ev: ffi::sctp_shutdown_event = read_struct(buf)?;
+                Ok(SctpNotification::Shutdown {
+                    assoc_id: ev.sse_assoc_id,
+                })
+            }
+            ffi::SCTP_ADAPTATION_INDICATION => {
+                let ev: ffi::sctp_adaptation_event = read_struct(buf)?;
+                Ok(SctpNotification::AdaptationIndication {
+                    assoc_id: ev.sai_assoc_id,
+                    indication: ev.sai_adaptation_ind,
+                })
+            }
+            ffi::SCTP_SENDER_DRY_EVENT => {
+                let ev: ffi::sctp_sender_dry_event = read_struct(buf)?;
+                Ok(SctpNotification::SenderDry {
+                    assoc_id: ev.sender_dry_assoc_id,
+                })
+            }
+            ffi::SCTP_STREAM_RESET_EVENT => {
+                let ev: ffi::sctp_stream_reset_event = read_struct(buf)?;
+                let streams =
+                    trailing_data::<ffi::sctp_stream_reset_event>(buf, ev.strreset_length)
+                        .chunks_exact(size_of::<u16>())
+                        .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+                        .collect();
+                Ok(SctpNotification::StreamReset {
+                    assoc_id: ev.strreset_assoc_id,
+                    incoming: ev.strreset_flags & ffi::SCTP_STREAM_RESET_INCOMING_SSN != 0,
+                    outgoing: ev.strreset_flags & ffi::SCTP_STREAM_RESET_OUTGOING_SSN != 0,
+                    denied: ev.strreset_flags & ffi::SCTP_STREAM_RESET_DENIED != 0,
+                    failed: ev.strreset_flags & ffi::SCTP_STREAM_RESET_FAILED != 0,
+                    streams,
+                })
+            }
+            _ => Ok(SctpNotification::Raw(buf.to_vec())),
+        }
+    }
+}
+
+/// Subscribe `sock` to every notification, and to the `sctp_sndrcvinfo` of data messages
+/// so that the data handed over along with notifications tells its stream
+pub(crate) fn subscribe_all(sock: &SctpSocket) -> Result<()> {
+    sock.subscribe_events(&EventSubscription::all())
+}
+
+/// Subscribe `sock` to every notification, and spawn a thread receiving from it and
+/// delivering the notifications and data messages into the returned channel.
+/// The thread stops when the channel is dropped, when the socket reaches end of file, or on
+/// the first receive error, which is then returned by the join handle
+pub(crate) fn spawn_handler(
+    sock: SctpSocket,
+) -> Result<(JoinHandle<Result<()>>, Receiver<IncomingMessage>)> {
+    subscribe_all(&sock)?;
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        let mut assembler = MessageAssembler::default();
+        loop {
+            let received = sock.recv_full(&mut buf)?;
+            if !received.is_notification && received.bytes == 0 {
+                return Ok(());
+            }
+            if let Some(incoming) = assembler.push(&buf, received)? {
+                if tx.send(incoming).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    });
+    Ok((handle, rx))
+}
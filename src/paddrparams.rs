@@ -0,0 +1,53 @@
+//! Per-destination path parameters (`SCTP_PEER_ADDR_PARAMS`)
+
+use crate::ffi;
+
+/// Parameters of the path to a peer address, or default parameters of an association.
+///
+/// When setting them, zero values and `None` leave the corresponding parameter unchanged.
+/// When reading them, every field is filled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PeerAddrParams {
+    /// Heartbeat interval, in milliseconds
+    pub heartbeat_interval: u32,
+    /// Whether heartbeats are sent
+    pub heartbeats: Option<bool>,
+    /// Number of retransmissions before the path is considered unreachable
+    pub path_max_retransmits: u16,
+    /// Path MTU. Only applied when path MTU discovery is disabled
+    pub path_mtu: u32,
+    /// Whether path MTU discovery is enabled
+    pub pmtu_discovery: Option<bool>,
+}
+
+impl PeerAddrParams {
+    /// Fill the parameters of `raw`, leaving its association and address untouched
+    pub(crate) fn fill_raw(&self, raw: &mut ffi::sctp_paddrparams) {
+        let mut flags = 0;
+        match self.heartbeats {
+            Some(true) => flags |= ffi::SPP_HB_ENABLE,
+            Some(false) => flags |= ffi::SPP_HB_DISABLE,
+            None => {}
+        }
+        match self.pmtu_discovery {
+            Some(true) => flags |= ffi::SPP_PMTUD_ENABLE,
+            Some(false) => flags |= ffi::SPP_PMTUD_DISABLE,
+            None => {}
+        }
+        raw.spp_hbinterval = self.heartbeat_interval;
+        raw.spp_pathmaxrxt = self.path_max_retransmits;
+        raw.spp_pathmtu = self.path_mtu;
+        raw.spp_flags = flags;
+    }
+
+    pub(crate) fn from_raw(raw: &ffi::sctp_paddrparams) -> PeerAddrParams {
+        let flags = raw.spp_flags;
+        PeerAddrParams {
+            heartbeat_interval: raw.spp_hbinterval,
+            heartbeats: Some(flags & ffi::SPP_HB_ENABLE != 0),
+            path_max_retransmits: raw.spp_pathmaxrxt,
+            path_mtu: raw.spp_pathmtu,
+            pmtu_discovery: Some(flags & ffi::SPP_PMTUD_ENABLE != 0),
+        }
+    }
+}
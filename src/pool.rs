@@ -0,0 +1,134 @@
+//! Pool of established one-to-one streams, reused across requests to the same peer
+
+use std::collections::HashMap;
+use std::io::Result;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+use crate::sctpsock::RawSocketAddr;
+use crate::status::AssocState;
+use crate::SctpStream;
+
+type IdleStreams = Arc<Mutex<HashMap<SocketAddr, Vec<SctpStream>>>>;
+
+/// A pool of idle, established `SctpStream`s keyed by peer address.
+///
+/// Streams are handed out with `get`, and go back to the pool when the returned
+/// `PooledStream` is dropped. Before being handed out again, a stream is checked to still be
+/// in the `Established` state, otherwise it is discarded and a new association is created.
+/// The pool can be cloned cheaply, clones share the same idle streams
+#[derive(Clone)]
+pub struct SctpConnectionPool {
+    idle: IdleStreams,
+    max_idle_per_peer: usize,
+}
+
+impl Default for SctpConnectionPool {
+    fn default() -> SctpConnectionPool {
+        SctpConnectionPool::new()
+    }
+}
+
+impl SctpConnectionPool {
+    /// Create an empty pool, keeping at most 8 idle streams per peer
+    pub fn new() -> SctpConnectionPool {
+        SctpConnectionPool::with_max_idle(8)
+    }
+
+    /// Create an empty pool, keeping at most `max_idle_per_peer` idle streams per peer.
+    /// Streams returned to a peer whose idle list is full are closed
+    pub fn with_max_idle(max_idle_per_peer: usize) -> SctpConnectionPool {
+        SctpConnectionPool {
+            idle: Arc::new(Mutex::new(HashMap::new())),
+            max_idle_per_peer,
+        }
+    }
+
+    /// Get a stream connected to `address`, reusing an idle one if any is still established,
+    /// or connecting a new one otherwise
+    pub fn get<A: ToSocketAddrs>(&self, address: A) -> Result<PooledStream> {
+        let addr = SocketAddr::from_addr(&address)?;
+        while let Some(stream) = self.take_idle(&addr) {
+            if matches!(stream.state(), Ok(AssocState::Established)) {
+                return Ok(self.wrap(addr, stream));
+            }
+        }
+        let stream = SctpStream::connect(addr)?;
+        Ok(self.wrap(addr, stream))
+    }
+
+    /// Get the number of idle streams kept for `address`
+    pub fn idle_count<A: ToSocketAddrs>(&self, address: A) -> Result<usize> {
+        let addr = SocketAddr::from_addr(&address)?;
+        let idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(idle.get(&addr).map_or(0, Vec::len))
+    }
+
+    /// Close all the idle streams
+    pub fn clear(&self) {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    fn take_idle(&self, addr: &SocketAddr) -> Option<SctpStream> {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        idle.get_mut(addr).and_then(Vec::pop)
+    }
+
+    fn wrap(&self, addr: SocketAddr, stream: SctpStream) -> PooledStream {
+        PooledStream {
+            stream: Some(stream),
+            addr,
+            pool: self.clone(),
+        }
+    }
+
+    fn put_back(&self, addr: SocketAddr, stream: SctpStream) {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        let streams = idle.entry(addr).or_default();
+        if streams.len() < self.max_idle_per_peer {
+            streams.push(stream);
+        }
+    }
+}
+
+/// A stream borrowed from an `SctpConnectionPool`. It dereferences to the underlying
+/// `SctpStream`, and goes back to the pool when dropped
+pub struct PooledStream {
+    stream: Option<SctpStream>,
+    addr: SocketAddr,
+    pool: SctpConnectionPool,
+}
+
+impl PooledStream {
+    /// Get the address of the peer this stream is connected to
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Close the stream instead of returning it to the pool, e.g. after a protocol error
+    /// left the connection in an unknown state
+    pub fn discard(mut self) {
+        self.stream.take();
+    }
+}
+
+impl Deref for PooledStream {
+    type Target = SctpStream;
+
+    fn deref(&self) -> &SctpStream {
+        self.stream
+            .as_ref()
+            .expect("pooled stream already released")
+    }
+}
+
+impl Drop for PooledStream {
+    fn drop(&mut self) {
+        if let Some(stream) = self.stream.take() {
+            if matches!(stream.state(), Ok(AssocState::Established)) {
+                self.pool.put_back(self.addr, stream);
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Channel for urgent messages, delivered ahead of bulk data
+
+use std::io::Result;
+use std::net::SocketAddr;
+
+use crate::ffi;
+use crate::SctpStream;
+
+/// A dedicated SCTP stream of a `SctpStream` for urgent messages, which jump ahead of the
+/// data queued on the other streams.
+///
+/// Under the hood, the association is switched to the priority stream scheduler
+/// (`SCTP_SS_PRIO`). Every stream starts with the highest priority (0): the urgent stream
+/// keeps it, while the other outbound streams are given a lower one (1), so that urgent
+/// messages are sent first. They are also sent unordered (`SCTP_UNORDERED`), so the peer
+/// delivers them as soon as they arrive instead of waiting for earlier messages of the
+/// stream. Other streams remain ordered. Streams added afterwards with `add_streams` get the
+/// highest priority, like the urgent one.
+/// The priority scheduler requires Linux 4.19 or newer
+pub struct PriorityChannel<'a> {
+    stream: &'a SctpStream,
+    sid: u16,
+}
+
+impl<'a> PriorityChannel<'a> {
+    /// Dedicate the SCTP stream `sid` of `stream` to urgent messages
+    pub fn new(stream: &'a SctpStream, sid: u16) -> Result<PriorityChannel<'a>> {
+        let outstreams = stream.0.status(0)?.outstreams;
+        stream.0.set_stream_scheduler(0, ffi::SCTP_SS_PRIO)?;
+        for other in (0..outstreams).filter(|other| *other != sid) {
+            stream.0.set_stream_scheduler_value(0, other, 1)?;
+        }
+        stream.0.set_stream_scheduler_value(0, sid, 0)?;
+        Ok(PriorityChannel { stream, sid })
+    }
+
+    /// Get the SCTP stream urgent messages are sent on
+    pub fn stream_id(&self) -> u16 {
+        self.sid
+    }
+
+    /// Get the stream this channel sends on
+    pub fn get_ref(&self) -> &'a SctpStream {
+        self.stream
+    }
+
+    /// Send an urgent message. On success, returns the quantity of bytes sent
+    pub fn send_urgent(&self, msg: &[u8]) -> Result<usize> {
+        self.stream.0.sendmsg_flags::<SocketAddr>(
+            msg,
+            None,
+            0,
+            sctp_sys::SCTP_UNORDERED,
+            self.sid,
+            0,
+        )
+    }
+}
@@ -0,0 +1,77 @@
+//! Runtime detection of the SCTP features supported by the kernel
+
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Result};
+
+use libc::AF_INET;
+use sctp_sys::{SOCK_SEQPACKET, SOL_SCTP};
+
+use crate::ffi;
+use crate::sctpsock::SctpSocket;
+
+/// Support status of a socket option
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionSupport {
+    /// The option is known by the kernel
+    Supported,
+    /// The option is not known by the kernel (`ENOPROTOOPT`)
+    NotSupported,
+    /// The option exists, but the calling process is not allowed to use it
+    PermissionDenied,
+}
+
+/// Options probed by `probe_features`, by feature name
+const FEATURES: &[(&str, libc::c_int, libc::c_int)] = &[
+    ("nodelay", SOL_SCTP, sctp_sys::SCTP_NODELAY),
+    ("initmsg", SOL_SCTP, sctp_sys::SCTP_INITMSG),
+    ("autoclose", SOL_SCTP, sctp_sys::SCTP_AUTOCLOSE),
+    ("events", SOL_SCTP, sctp_sys::SCTP_EVENTS),
+    ("maxseg", SOL_SCTP, sctp_sys::SCTP_MAXSEG),
+    ("status", SOL_SCTP, sctp_sys::SCTP_STATUS),
+    (
+        "peer_addr_params",
+        SOL_SCTP,
+        sctp_sys::SCTP_PEER_ADDR_PARAMS,
+    ),
+    ("delayed_sack", SOL_SCTP, sctp_sys::SCTP_DELAYED_SACK),
+    ("hmac_ident", SOL_SCTP, sctp_sys::SCTP_HMAC_IDENT),
+    ("assoc_stats", SOL_SCTP, sctp_sys::SCTP_GET_ASSOC_STATS),
+    ("recv_rcvinfo", SOL_SCTP, sctp_sys::SCTP_RECVRCVINFO),
+    ("recv_nxtinfo", SOL_SCTP, sctp_sys::SCTP_RECVNXTINFO),
+    ("default_sndinfo", SOL_SCTP, sctp_sys::SCTP_DEFAULT_SNDINFO),
+    ("pr_sctp", SOL_SCTP, ffi::SCTP_PR_SUPPORTED),
+    ("default_prinfo", SOL_SCTP, ffi::SCTP_DEFAULT_PRINFO),
+    ("reconfig", SOL_SCTP, ffi::SCTP_RECONFIG_SUPPORTED),
+    ("stream_reset", SOL_SCTP, ffi::SCTP_ENABLE_STREAM_RESET),
+    ("stream_scheduler", SOL_SCTP, ffi::SCTP_STREAM_SCHEDULER),
+    ("interleaving", SOL_SCTP, ffi::SCTP_INTERLEAVING_SUPPORTED),
+    ("asconf", SOL_SCTP, ffi::SCTP_ASCONF_SUPPORTED),
+    ("auth", SOL_SCTP, ffi::SCTP_AUTH_SUPPORTED),
+    ("ecn", SOL_SCTP, ffi::SCTP_ECN_SUPPORTED),
+];
+
+/// Check if SCTP sockets can be created on this host. When they can't, the error returned
+/// by the socket constructors tells why
+pub fn is_available() -> bool {
+    SctpSocket::new(AF_INET, SOCK_SEQPACKET).is_ok()
+}
+
+/// Probe the SCTP features supported by the running kernel, by feature name.
+/// Fails if SCTP sockets cannot be created at all
+pub fn probe_features() -> Result<BTreeMap<&'static str, OptionSupport>> {
+    let sock = SctpSocket::new(AF_INET, SOCK_SEQPACKET)?;
+    let mut features: BTreeMap<_, _> = FEATURES
+        .iter()
+        .map(|&(feature, level, name)| (feature, sock.probe_option(level, name)))
+        .collect();
+    // SO_ZEROCOPY can be read on any socket, only enabling it tells if SCTP supports it
+    let zerocopy = match sock.set_zerocopy(true) {
+        Ok(()) => OptionSupport::Supported,
+        Err(ref err) if err.kind() == ErrorKind::PermissionDenied => {
+            OptionSupport::PermissionDenied
+        }
+        Err(_) => OptionSupport::NotSupported,
+    };
+    features.insert("zerocopy", zerocopy);
+    Ok(features)
+}
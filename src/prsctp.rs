@@ -0,0 +1,49 @@
+//! Partial reliability (PR-SCTP) policies
+
+use std::io::{Error, ErrorKind, Result};
+use std::time::Duration;
+
+use crate::ffi;
+
+/// Partial reliability policy, telling when the stack may abandon a message
+/// instead of retransmitting it. Requires the peer to support PR-SCTP, otherwise
+/// messages are delivered reliably
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrPolicy {
+    /// Fully reliable delivery
+    None,
+    /// Abandon the message once its lifetime expired (millisecond precision)
+    Ttl(Duration),
+    /// Abandon the message after the given number of retransmissions
+    Rtx(u32),
+    /// Abandon the message when the send buffer is full and a message with a
+    /// higher priority (lower value) needs room
+    Buffer(u32),
+}
+
+impl PrPolicy {
+    /// Raw policy flag and value of this policy
+    pub(crate) fn to_raw(self) -> Result<(u16, u32)> {
+        match self {
+            PrPolicy::None => Ok((ffi::SCTP_PR_SCTP_NONE, 0)),
+            PrPolicy::Ttl(lifetime) => {
+                let ms = u32::try_from(lifetime.as_millis()).map_err(|_| {
+                    Error::new(ErrorKind::InvalidInput, "PR-SCTP lifetime is too long")
+                })?;
+                Ok((ffi::SCTP_PR_SCTP_TTL, ms))
+            }
+            PrPolicy::Rtx(count) => Ok((ffi::SCTP_PR_SCTP_RTX, count)),
+            PrPolicy::Buffer(prio) => Ok((ffi::SCTP_PR_SCTP_PRIO, prio)),
+        }
+    }
+
+    /// Build a policy from its raw flag and value
+    pub(crate) fn from_raw(policy: u16, value: u32) -> PrPolicy {
+        match policy & ffi::SCTP_PR_SCTP_MASK {
+            ffi::SCTP_PR_SCTP_TTL => PrPolicy::Ttl(Duration::from_millis(value as u64)),
+            ffi::SCTP_PR_SCTP_RTX => PrPolicy::Rtx(value),
+            ffi::SCTP_PR_SCTP_PRIO => PrPolicy::Buffer(value),
+            _ => PrPolicy::None,
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! One-to-one stream which transparently reconnects when its association is lost
+
+use std::io::prelude::*;
+use std::io::{Error, ErrorKind, Result};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+use crate::notifications::{AssocChangeState, EventSubscription, SctpNotification};
+use crate::{resolve_addrs, SctpStream};
+
+type ReconnectCallback = Box<dyn FnMut(&SctpStream) -> Result<()> + Send>;
+
+/// Check if `err` means the association is gone. Only the `ETIMEDOUT` reported by the
+/// kernel counts as a timeout: an expired send timeout means the peer is slow, not gone
+fn is_connection_lost(err: &Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+    ) || err.raw_os_error() == Some(libc::ETIMEDOUT)
+}
+
+/// Check if the notification in `buf` reports the loss of the association
+fn is_comm_lost(buf: &[u8]) -> bool {
+    matches!(
+        SctpNotification::parse(buf),
+        Ok(SctpNotification::AssocChange {
+            state: AssocChangeState::CommLost | AssocChangeState::ShutdownComplete,
+            ..
+        })
+    )
+}
+
+/// A one-to-one SCTP stream which survives the loss of its association.
+///
+/// When the association is lost (a `COMM_LOST` notification is received, the peer shuts it
+/// down, or a send/receive fails with a connection error), the stream reconnects to the
+/// original list of addresses, waiting with an exponential backoff between attempts.
+/// After each successful reconnection, the callback registered with `on_reconnect` is run so
+/// that the application can re-establish its protocol state. Messages which were in flight
+/// when the association was lost are not retransmitted on the new association.
+pub struct ReconnectingStream {
+    addrs: Vec<SocketAddr>,
+    stream: SctpStream,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    max_attempts: Option<u32>,
+    on_reconnect: Option<ReconnectCallback>,
+    reconnects: u64,
+}
+
+impl ReconnectingStream {
+    /// Connect to a remote endpoint having one or more addresses
+    pub fn connect<A: ToSocketAddrs>(addresses: &[A]) -> Result<ReconnectingStream> {
+        let (_, addrs) = resolve_addrs(addresses)?;
+        let stream = Self::open(&addrs)?;
+        Ok(ReconnectingStream {
+            addrs,
+            stream,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: Some(10),
+            on_reconnect: None,
+            reconnects: 0,
+        })
+    }
+
+    fn open(addrs: &[SocketAddr]) -> Result<SctpStream> {
+        let stream = SctpStream::connectx(addrs)?;
+        stream.0.subscribe_events(&EventSubscription {
+            data_io: true,
+            association: true,
+            ..Default::default()
+        })?;
+        Ok(stream)
+    }
+
+    /// Set the delay before the first reconnection attempt, and the maximum delay between
+    /// two attempts. The delay doubles after each failed attempt
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> ReconnectingStream {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the number of reconnection attempts before giving up. `None` retries forever
+    pub fn with_max_attempts(mut self, attempts: Option<u32>) -> ReconnectingStream {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Set a callback run on the new stream after each successful reconnection.
+    /// If the callback fails, the reconnection is considered as failed
+    pub fn on_reconnect<F>(mut self, f: F) -> ReconnectingStream
+    where
+        F: FnMut(&SctpStream) -> Result<()> + Send + 'static,
+    {
+        self.on_reconnect = Some(Box::new(f));
+        self
+    }
+
+    /// Get the current underlying stream
+    pub fn get_ref(&self) -> &SctpStream {
+        &self.stream
+    }
+
+    /// Get the number of times the stream reconnected
+    pub fn reconnect_count(&self) -> u64 {
+        self.reconnects
+    }
+
+    /// Drop the current association and connect a new one
+    pub fn reconnect(&mut self) -> Result<()> {
+        let mut delay = self.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let res = Self::open(&self.addrs).and_then(|stream| {
+                if let Some(ref mut f) = self.on_reconnect {
+                    f(&stream)?;
+                }
+                Ok(stream)
+            });
+            match res {
+                Ok(stream) => {
+                    self.stream = stream;
+                    self.reconnects += 1;
+                    return Ok(());
+                }
+                Err(err) => {
+                    if self.max_attempts.is_some_and(|max| attempt >= max) {
+                        return Err(err);
+                    }
+                }
+            }
+            thread::sleep(delay);
+            delay = std::cmp::min(delay * 2, self.max_backoff);
+        }
+    }
+
+    fn retry<T, F>(&mut self, mut f: F) -> Result<T>
+    where
+        F: FnMut(&SctpStream) -> Result<T>,
+    {
+        match f(&self.stream) {
+            Err(ref err) if is_connection_lost(err) => {
+                self.reconnect()?;
+                f(&self.stream)
+            }
+            res => res,
+        }
+    }
+
+    /// Send bytes on the specified SCTP stream, reconnecting first if the association is lost.
+    /// On success, returns the quantity of bytes sent
+    pub fn sendmsg(&mut self, msg: &[u8], stream: u16) -> Result<usize> {
+        self.retry(|s| s.sendmsg(msg, stream))
+    }
+
+    /// Send bytes on the specified SCTP stream with the payload protocol identifier `ppid`,
+    /// reconnecting first if the association is lost. On success, returns the quantity of bytes sent
+    pub fn sendmsg_ppid(&mut self, msg: &[u8], ppid: u32, stream: u16) -> Result<usize> {
+        self.retry(|s| s.sendmsg_ppid(msg, ppid, stream))
+    }
+
+    /// Read bytes, waiting on a new association if the current one is lost.
+    /// On success, return a tuple with the quantity of bytes received and the stream
+    /// they were received on
+    pub fn recvmsg(&mut self, msg: &mut [u8]) -> Result<(usize, u16)> {
+        loop {
+            match self.stream.recv_full(msg) {
+                Ok(received) if received.is_notification => {
+                    if is_comm_lost(&msg[..received.bytes]) {
+                        self.reconnect()?;
+                    }
+                }
+                // Peer shut the association down
+                Ok(received) if received.bytes == 0 => self.reconnect()?,
+                Ok(received) => return Ok((received.bytes, received.stream.unwrap_or(0))),
+                Err(ref err) if is_connection_lost(err) => self.reconnect()?,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Read for ReconnectingStream {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.recvmsg(buf).map(|(size, _)| size)
+    }
+}
+
+impl Write for ReconnectingStream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.sendmsg(buf, 0)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
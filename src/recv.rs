@@ -0,0 +1,197 @@
+//! Full-featured receive path, exposing every piece of information the kernel
+//! provides along with a message
+
+use std::net::SocketAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sctp_sys::{sctp_assoc_t, sctp_sndrcvinfo};
+
+use crate::ffi;
+use crate::notifications::SctpNotification;
+
+/// Something received with `recv_event`: either user data or a notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecvEvent {
+    /// User data was written in the receive buffer
+    Data {
+        /// Quantity of bytes received
+        len: usize,
+        /// Stream the data was received on
+        stream: u16,
+        /// Address of the peer which sent the data
+        addr: SocketAddr,
+    },
+    /// A notification was received
+    Notification(SctpNotification),
+}
+
+impl RecvEvent {
+    /// Build the event for a buffer received by `sctp_recvmsg` with `flags`
+    pub(crate) fn new(
+        buf: &[u8],
+        len: usize,
+        stream: u16,
+        addr: SocketAddr,
+        flags: libc::c_int,
+    ) -> std::io::Result<RecvEvent> {
+        if flags & ffi::MSG_NOTIFICATION != 0 {
+            Ok(RecvEvent::Notification(SctpNotification::parse(
+                &buf[..len],
+            )?))
+        } else {
+            Ok(RecvEvent::Data { len, stream, addr })
+        }
+    }
+}
+
+/// Something received by a notification handler thread or a `NotificationStream`.
+/// Notifications and data share the same receive queue, so the data messages read while
+/// waiting for notifications are handed over as well, rather than being lost
+#[derive(Debug, Clone)]
+pub enum IncomingMessage {
+    /// A whole data message, along with the information received with its last piece
+    Data(Vec<u8>, ReceivedMessage),
+    /// A notification
+    Notification(SctpNotification),
+}
+
+/// Rebuild the messages received in several pieces into `IncomingMessage` values
+#[derive(Default)]
+pub(crate) struct MessageAssembler {
+    message: Vec<u8>,
+}
+
+impl MessageAssembler {
+    /// Forget the pieces of the message being rebuilt, e.g. after a receive error
+    #[cfg(all(target_os = "linux", feature = "tokio"))]
+    pub(crate) fn reset(&mut self) {
+        self.message.clear();
+    }
+
+    /// Add the piece just received in `buf`, described by `received`.
+    /// Returns the whole message once its last piece was added
+    pub(crate) fn push(
+        &mut self,
+        buf: &[u8],
+        mut received: ReceivedMessage,
+    ) -> std::io::Result<Option<IncomingMessage>> {
+        self.message.extend_from_slice(&buf[..received.bytes]);
+        if !received.end_of_record {
+            return Ok(None);
+        }
+        let message = std::mem::take(&mut self.message);
+        if received.is_notification {
+            Ok(Some(IncomingMessage::Notification(
+                SctpNotification::parse(&message)?,
+            )))
+        } else {
+            received.bytes = message.len();
+            Ok(Some(IncomingMessage::Data(message, received)))
+        }
+    }
+}
+
+/// Information about the message following the one just received.
+/// Only available when enabled with `set_recv_nxtinfo`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NextInfo {
+    /// Stream the next message was received on
+    pub stream: u16,
+    /// Raw flags of the next message
+    pub flags: u16,
+    /// Payload protocol identifier of the next message
+    pub ppid: u32,
+    /// Length of the next message
+    pub length: u32,
+    /// Association the next message belongs to
+    pub assoc_id: sctp_assoc_t,
+}
+
+/// A message received with `recv_full`, along with all its ancillary information.
+/// Fields which depend on an option being enabled on the socket are `None` when the
+/// kernel didn't provide them
+#[derive(Debug, Clone, Default)]
+pub struct ReceivedMessage {
+    /// Quantity of bytes received
+    pub bytes: usize,
+    /// Raw `msg_flags` returned by the kernel
+    pub flags: libc::c_int,
+    /// Whether the buffer holds the end of a message (`MSG_EOR`)
+    pub end_of_record: bool,
+    /// Whether the buffer holds a notification rather than user data
+    pub is_notification: bool,
+    /// Address of the peer which sent the message
+    pub addr: Option<SocketAddr>,
+    /// Stream the message was received on
+    pub stream: Option<u16>,
+    /// Stream sequence number of the message
+    pub ssn: Option<u16>,
+    /// Raw SCTP flags of the message (`SCTP_UNORDERED`, ...)
+    pub sctp_flags: Option<u16>,
+    /// Payload protocol identifier, in host byte order
+    pub ppid: Option<u32>,
+    /// Transmission sequence number of the message
+    pub tsn: Option<u32>,
+    /// Context of the message
+    pub context: Option<u32>,
+    /// Association the message belongs to
+    pub assoc_id: Option<sctp_assoc_t>,
+    /// Reception time, when `SO_TIMESTAMP` is enabled
+    pub timestamp: Option<SystemTime>,
+    /// Information about the next message, when `SCTP_RECVNXTINFO` is enabled
+    pub next: Option<NextInfo>,
+}
+
+impl ReceivedMessage {
+    /// Fill the fields provided by the control messages of `msg`
+    ///
+    /// # Safety
+    ///
+    /// `msg` must have been filled by a successful `recvmsg` call
+    pub(crate) unsafe fn parse_cmsgs(&mut self, msg: &libc::msghdr) {
+        let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+        while !cmsg.is_null() {
+            let data = libc::CMSG_DATA(cmsg);
+            match ((*cmsg).cmsg_level, (*cmsg).cmsg_type) {
+                (sctp_sys::SOL_SCTP, ffi::SCTP_SNDRCV) => {
+                    let info = std::ptr::read_unaligned(data as *const sctp_sndrcvinfo);
+                    self.stream = Some(info.sinfo_stream);
+                    self.ssn = Some(info.sinfo_ssn);
+                    self.sctp_flags = Some(info.sinfo_flags);
+                    self.ppid = Some(u32::from_be(info.sinfo_ppid));
+                    self.tsn = Some(info.sinfo_tsn);
+                    self.context = Some(info.sinfo_context);
+                    self.assoc_id = Some(info.sinfo_assoc_id);
+                }
+                (sctp_sys::SOL_SCTP, ffi::SCTP_RCVINFO) => {
+                    let info = std::ptr::read_unaligned(data as *const ffi::sctp_rcvinfo);
+                    self.stream = Some(info.rcv_sid);
+                    self.ssn = Some(info.rcv_ssn);
+                    self.sctp_flags = Some(info.rcv_flags);
+                    self.ppid = Some(u32::from_be(info.rcv_ppid));
+                    self.tsn = Some(info.rcv_tsn);
+                    self.context = Some(info.rcv_context);
+                    self.assoc_id = Some(info.rcv_assoc_id);
+                }
+                (sctp_sys::SOL_SCTP, ffi::SCTP_NXTINFO) => {
+                    let info = std::ptr::read_unaligned(data as *const ffi::sctp_nxtinfo);
+                    self.next = Some(NextInfo {
+                        stream: info.nxt_sid,
+                        flags: info.nxt_flags,
+                        ppid: u32::from_be(info.nxt_ppid),
+                        length: info.nxt_length,
+                        assoc_id: info.nxt_assoc_id,
+                    });
+                }
+                (libc::SOL_SOCKET, libc::SCM_TIMESTAMP) => {
+                    let tv = std::ptr::read_unaligned(data as *const libc::timeval);
+                    self.timestamp = Some(
+                        UNIX_EPOCH + Duration::new(tv.tv_sec as u64, (tv.tv_usec as u32) * 1000),
+                    );
+                }
+                _ => {}
+            }
+            cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+        }
+    }
+}
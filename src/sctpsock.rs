@@ -3,6 +3,11 @@ use std::mem::{size_of, MaybeUninit};
 use std::net::{
     Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs,
 };
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(target_os = "linux")]
+use std::time::Instant;
 
 // import macros from lib
 #[cfg(target_os = "linux")]
@@ -12,6 +17,25 @@ use crate::{sctp_syscall, syscall};
 #[cfg(target_os = "linux")]
 use crate::mio_unix::{socket_addr, to_socket_addr};
 
+#[cfg(target_os = "linux")]
+use crate::cancel::{Cancelled, ShutdownToken};
+#[cfg(target_os = "linux")]
+use crate::errqueue::{self, SctpExtError};
+#[cfg(target_os = "linux")]
+use crate::ffi;
+#[cfg(target_os = "linux")]
+use crate::notifications::{EventSubscription, SctpNotification};
+#[cfg(target_os = "linux")]
+use crate::paddrparams::PeerAddrParams;
+#[cfg(target_os = "linux")]
+use crate::probe::OptionSupport;
+#[cfg(target_os = "linux")]
+use crate::prsctp::PrPolicy;
+#[cfg(target_os = "linux")]
+use crate::recv::ReceivedMessage;
+#[cfg(target_os = "linux")]
+use crate::status::SctpStatus;
+
 #[cfg(target_os = "linux")]
 use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
 
@@ -92,6 +116,117 @@ impl BindOp {
     }
 }
 
+impl RecvInfo {
+    #[cfg(target_os = "linux")]
+    fn from_received(received: ReceivedMessage) -> Result<RecvInfo> {
+        let addr = received
+            .addr
+            .ok_or_else(|| Error::from(ErrorKind::InvalidInput))?;
+        Ok(RecvInfo {
+            bytes: received.bytes,
+            stream: received.stream.unwrap_or(0),
+            ppid: received.ppid.unwrap_or(0),
+            flags: received.flags,
+            assoc_id: received.assoc_id.unwrap_or(0),
+            unordered: received
+                .sctp_flags
+                .is_some_and(|flags| flags & sctp_sys::SCTP_UNORDERED as u16 != 0),
+            context: received.context.unwrap_or(0),
+            addr,
+        })
+    }
+
+    /// Check if the buffer holds the end of a message (`MSG_EOR`). When a message is larger
+    /// than the receive buffer, it is delivered in several parts, and only the last one
+    /// has this flag set
+    #[cfg(target_os = "linux")]
+    pub fn end_of_record(&self) -> bool {
+        self.flags & libc::MSG_EOR != 0
+    }
+
+    /// Check if the buffer holds a notification rather than user data (`MSG_NOTIFICATION`)
+    #[cfg(target_os = "linux")]
+    pub fn is_notification(&self) -> bool {
+        self.flags & ffi::MSG_NOTIFICATION != 0
+    }
+}
+
+/// A message to send with `sendmmsg`
+#[derive(Debug, Clone, Copy)]
+pub struct BatchMessage<'a> {
+    /// Content of the message
+    pub data: &'a [u8],
+    /// Destination of the message, `None` on connected sockets
+    pub addr: Option<SocketAddr>,
+    /// Stream to send the message on
+    pub stream: u16,
+    /// Payload protocol identifier, in host byte order
+    pub ppid: u32,
+}
+
+/// Turn the error returned by the kernel when it doesn't know `SCTP_PLUGGABLE_CC` into an
+/// explicit `ErrorKind::Unsupported` error
+#[cfg(target_os = "linux")]
+fn cc_unsupported<T>(res: Result<T>) -> Result<T> {
+    match res {
+        Err(ref err) if err.raw_os_error() == Some(libc::ENOPROTOOPT) => Err(Error::new(
+            ErrorKind::Unsupported,
+            "Selecting the congestion control of SCTP sockets is not supported by this kernel",
+        )),
+        res => res,
+    }
+}
+
+/// Build the error returned when creating a socket of type `sock_type` fails with
+/// `EPROTONOSUPPORT`, telling apart a host without SCTP from an unsupported socket type
+#[cfg(target_os = "linux")]
+fn protocol_unsupported(family: libc::c_int, sock_type: libc::c_int) -> Error {
+    let other_type = if sock_type == libc::SOCK_STREAM {
+        sctp_sys::SOCK_SEQPACKET
+    } else {
+        libc::SOCK_STREAM
+    };
+    let msg = if SctpSocket::new_raw(family, other_type).is_ok() {
+        "SCTP is available, but not with this socket type"
+    } else {
+        "SCTP is not available on this host, the sctp kernel module may need to be \
+         loaded (modprobe sctp)"
+    };
+    Error::new(ErrorKind::Unsupported, msg)
+}
+
+/// Check if `local` is the same address as `addr`. A port of 0 in `addr` matches any port
+fn same_addr(local: &SocketAddr, addr: &SocketAddr) -> bool {
+    local.ip().to_canonical() == addr.ip().to_canonical()
+        && (addr.port() == 0 || local.port() == addr.port())
+}
+
+/// A message received with `recvmsg_info`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvInfo {
+    /// Quantity of bytes received
+    pub bytes: usize,
+    /// Stream the message was received on
+    pub stream: u16,
+    /// Payload protocol identifier, in host byte order. Only filled when data I/O events
+    /// are subscribed to (see `EventSubscription::data_io`) or `SCTP_RCVINFO` is enabled
+    /// (see `set_recv_rcvinfo`), 0 otherwise
+    pub ppid: u32,
+    /// Raw flags of the message (`MSG_NOTIFICATION`, `MSG_EOR`, ...)
+    pub flags: libc::c_int,
+    /// Association the message belongs to. Filled under the same conditions as `ppid`,
+    /// 0 otherwise
+    pub assoc_id: sctp_sys::sctp_assoc_t,
+    /// Whether the message was sent unordered. Filled under the same conditions as `ppid`,
+    /// `false` otherwise
+    pub unordered: bool,
+    /// Context of the association the message was received on, as set locally with the
+    /// `SCTP_CONTEXT` option. Filled under the same conditions as `ppid`, 0 otherwise
+    pub context: u32,
+    /// Address of the peer which sent the message
+    pub addr: SocketAddr,
+}
+
 enum SctpAddrType {
     Local,
     Peer,
@@ -178,14 +313,122 @@ impl RawSocketAddr for SocketAddr {
     }
 }
 
+/// Pack `addresses` back to back into a buffer of `sockaddr_in` / `sockaddr_in6` structures,
+/// as expected by `sctp_bindx` and `sctp_connectx`. Each address takes exactly the size of
+/// its own family's structure. Both sizes being multiples of 4, the buffer is made of `u32`
+/// so that every packed structure is properly aligned
+#[cfg(target_os = "linux")]
+fn pack_addrs<A: ToSocketAddrs>(addresses: &[A]) -> Result<Vec<u32>> {
+    if addresses.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
+    }
+    let mut raw_addrs = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        raw_addrs.push(socket_addr(&SocketAddr::from_addr(address)?));
+    }
+    let total_len: usize = raw_addrs.iter().map(|&(_, len)| len as usize).sum();
+
+    let mut buf = vec![0u32; total_len / size_of::<u32>()];
+    let mut offset = 0;
+    for (raw_addr, raw_addr_length) in raw_addrs {
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                raw_addr.as_ptr() as *const u8,
+                (buf.as_mut_ptr() as *mut u8).add(offset),
+                raw_addr_length as usize,
+            )
+        };
+        offset += raw_addr_length as usize;
+    }
+    Ok(buf)
+}
+
+/// View a plain C structure as raw bytes
+#[cfg(target_os = "linux")]
+fn struct_bytes<T: Copy>(val: &T) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(val as *const T as *const u8, size_of::<T>()) }
+}
+
+/// Default size of the control buffers used to receive ancillary data
+#[cfg(target_os = "linux")]
+pub(crate) const CONTROL_LEN: usize = 512;
+
+/// Fail if the kernel had to drop ancillary data because the control buffer was too small
+#[cfg(target_os = "linux")]
+fn check_ctrunc(msg: &libc::msghdr) -> Result<()> {
+    if msg.msg_flags & libc::MSG_CTRUNC != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Ancillary data truncated (MSG_CTRUNC): control buffer is too small",
+        ));
+    }
+    Ok(())
+}
+
+/// Converts a Rust `SocketAddr` into a `sockaddr_storage`, as embedded in SCTP socket options
+#[cfg(target_os = "linux")]
+pub(crate) fn sockaddr_storage(addr: &SocketAddr) -> libc::sockaddr_storage {
+    let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+    let (raw_addr, raw_addr_length) = socket_addr(addr);
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            raw_addr.as_ptr() as *const u8,
+            &mut storage as *mut libc::sockaddr_storage as *mut u8,
+            raw_addr_length as usize,
+        )
+    };
+    storage
+}
+
+/// Maximum size of the received messages, shared by the clones of a socket
+#[derive(Default)]
+struct RecvLimit {
+    /// Maximum message size, 0 for no limit
+    max: AtomicUsize,
+    /// Bytes received so far for the message being received
+    current: AtomicUsize,
+    /// Set while the rest of a message exceeding the maximum size remains to be dropped
+    dropping: AtomicBool,
+}
+
 /// A High level wrapper around SCTP socket, of any kind
-pub struct SctpSocket(SOCKET);
+pub struct SctpSocket {
+    fd: SOCKET,
+    /// Whether `sendmsg` prefers `sendmsg(2)` over `sctp_sendmsg`
+    prefer_modern: AtomicBool,
+    /// Maximum size of received messages, set with `set_max_recv_message_size`
+    recv_limit: Arc<RecvLimit>,
+}
 
 impl SctpSocket {
-    /// Create a new SCTP socket
+    fn from_sock(sock: SOCKET) -> SctpSocket {
+        SctpSocket {
+            fd: sock,
+            prefer_modern: AtomicBool::new(cfg!(target_os = "linux")),
+            recv_limit: Arc::default(),
+        }
+    }
+
+    /// Create a new SCTP socket.
+    /// When the kernel doesn't support the protocol (`EPROTONOSUPPORT`), the error tells
+    /// whether SCTP is not available at all, or only not with this socket type
     pub fn new(family: libc::c_int, sock_type: libc::c_int) -> Result<SctpSocket> {
+        let sock = SctpSocket::new_raw(family, sock_type);
+        #[cfg(target_os = "linux")]
+        if let Err(ref err) = sock {
+            if err.raw_os_error() == Some(libc::EPROTONOSUPPORT) {
+                return Err(protocol_unsupported(family, sock_type));
+            }
+        }
+        sock
+    }
+
+    fn new_raw(family: libc::c_int, sock_type: libc::c_int) -> Result<SctpSocket> {
+        // Don't leak the socket into child processes
+        #[cfg(target_os = "linux")]
+        let sock_type = sock_type | libc::SOCK_CLOEXEC;
         unsafe {
-            Ok(SctpSocket(check_socket(socket(
+            Ok(SctpSocket::from_sock(check_socket(socket(
                 family,
                 sock_type,
                 sctp_sys::IPPROTO_SCTP,
@@ -197,7 +440,7 @@ impl SctpSocket {
     pub fn connect<A: ToSocketAddrs>(&self, address: A) -> Result<()> {
         let addrobj = SocketAddr::from_addr(&address)?;
         let (raw_addr, raw_addr_length) = socket_addr(&addrobj);
-        match syscall!(connect(self.0, raw_addr.as_ptr(), raw_addr_length)) {
+        match syscall!(connect(self.fd, raw_addr.as_ptr(), raw_addr_length)) {
             Err(err) if err.raw_os_error() != Some(EINPROGRESS) => Err(err),
             _ => Ok(()),
         }
@@ -205,104 +448,119 @@ impl SctpSocket {
 
     /// Connect the socket to multiple addresses
     pub fn connectx<A: ToSocketAddrs>(&self, addresses: &[A]) -> Result<sctp_sys::sctp_assoc_t> {
-        if addresses.is_empty() {
-            return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
-        }
-
-        let buf: *mut u8 = unsafe {
-            libc::malloc((addresses.len() * size_of::<libc::sockaddr_storage>()) as libc::size_t)
-                as *mut u8
-        };
-        if buf.is_null() {
-            return Err(Error::new(ErrorKind::Other, "Out of memory"));
-        }
-        let mut offset = 0isize;
-        for address in addresses {
-            let addrobj = SocketAddr::from_addr(address)?;
-            let (raw_addr, raw_addr_length) = socket_addr(&addrobj);
-            unsafe {
-                std::ptr::copy_nonoverlapping(
-                    raw_addr.as_ptr() as *mut u8,
-                    buf.offset(offset),
-                    raw_addr_length as usize,
-                )
-            };
-            offset += raw_addr_length as isize;
-        }
-
+        let mut buf = pack_addrs(addresses)?;
         let mut assoc: sctp_sys::sctp_assoc_t = 0;
 
-        match sctp_syscall!(sctp_connectx(
-            self.0,
-            buf as *mut sockaddr,
+        sctp_syscall!(sctp_connectx(
+            self.fd,
+            buf.as_mut_ptr() as *mut sockaddr,
             addresses.len() as i32,
             &mut assoc
-        )) {
-            Err(err) => {
-                unsafe { libc::free(buf as *mut libc::c_void) };
-                Err(err)
-            }
-            Ok(_) => {
-                unsafe { libc::free(buf as *mut libc::c_void) };
-                Ok(assoc)
-            }
-        }
+        ))?;
+        Ok(assoc)
     }
 
     /// Bind the socket to a single address
     pub fn bind<A: ToSocketAddrs>(&self, address: A) -> Result<()> {
         let addrobj = SocketAddr::from_addr(&address)?;
         let (raw_addr, raw_addr_length) = socket_addr(&addrobj);
-        syscall!(bind(self.0, raw_addr.as_ptr(), raw_addr_length))?;
+        syscall!(bind(self.fd, raw_addr.as_ptr(), raw_addr_length))?;
         Ok(())
     }
 
     /// Bind the socket on multiple addresses
     pub fn bindx<A: ToSocketAddrs>(&self, addresses: &[A], op: BindOp) -> Result<()> {
-        if addresses.is_empty() {
-            return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
+        let mut buf = pack_addrs(addresses)?;
+
+        sctp_syscall!(sctp_bindx(
+            self.fd,
+            buf.as_mut_ptr() as *mut sockaddr,
+            addresses.len() as i32,
+            op.flag()
+        ))?;
+        Ok(())
+    }
+
+    /// Check if the socket is bound to `address`. A port of 0 matches any port
+    pub fn is_bound_to(&self, address: SocketAddr) -> Result<bool> {
+        Ok(self
+            .local_addrs(0)?
+            .iter()
+            .any(|local| same_addr(local, &address)))
+    }
+
+    /// Add addresses to the bound ones at runtime. Fails if one of them is already bound
+    pub fn bindx_add(&self, addresses: &[SocketAddr]) -> Result<()> {
+        for addr in addresses {
+            if self.is_bound_to(*addr)? {
+                return Err(Error::new(
+                    ErrorKind::AddrInUse,
+                    format!("Already bound to {}", addr),
+                ));
+            }
         }
+        self.bindx(addresses, BindOp::AddAddr)
+    }
 
-        let buf: *mut u8 = unsafe {
-            libc::malloc((addresses.len() * size_of::<sockaddr_in6>()) as libc::size_t) as *mut u8
-        };
-        if buf.is_null() {
-            return Err(Error::new(ErrorKind::Other, "Out of memory"));
+    /// Remove addresses from the bound ones at runtime. Fails if one of them is not bound,
+    /// if no address would remain, or if no remaining address could reach the primary
+    /// destination of the peer
+    pub fn bindx_remove(&self, addresses: &[SocketAddr]) -> Result<()> {
+        let locals = self.local_addrs(0)?;
+        for addr in addresses {
+            if !locals.iter().any(|local| same_addr(local, addr)) {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("Not bound to {}", addr),
+                ));
+            }
         }
-        let mut offset = 0isize;
-        for address in addresses {
-            let addrobj = SocketAddr::from_addr(address)?;
-            let (raw_addr, raw_addr_length) = socket_addr(&addrobj);
-            unsafe {
-                std::ptr::copy_nonoverlapping(
-                    raw_addr.as_ptr() as *mut u8,
-                    buf.offset(offset),
-                    raw_addr_length as usize,
-                )
-            };
-            offset += raw_addr_length as isize;
+        let remaining: Vec<&SocketAddr> = locals
+            .iter()
+            .filter(|local| !addresses.iter().any(|addr| same_addr(local, addr)))
+            .collect();
+        if remaining.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Cannot remove the last bound address",
+            ));
         }
-
-        match sctp_syscall!(sctp_bindx(
-            self.0,
-            buf as *mut sockaddr,
-            addresses.len() as i32,
-            op.flag()
-        )) {
-            Err(err) => {
-                unsafe { libc::free(buf as *mut libc::c_void) };
-                Err(err)
+        #[cfg(target_os = "linux")]
+        if let Ok(Some(primary)) = self.status(0).map(|status| status.primary_addr) {
+            let primary_v4 = primary.ip().to_canonical().is_ipv4();
+            if !remaining
+                .iter()
+                .any(|local| local.ip().to_canonical().is_ipv4() == primary_v4)
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "No remaining address could reach the primary path",
+                ));
             }
-            Ok(_) => Ok(()),
         }
+        self.bindx(addresses, BindOp::RemAddr)
     }
 
     /// Listen
     pub fn listen(&self, backlog: libc::c_int) -> Result<()> {
-        syscall!(listen(self.0, backlog))?;
+        syscall!(listen(self.fd, backlog))?;
         Ok(())
     }
 
+    /// Detach the association `assoc` of a one-to-many socket into a new one-to-one socket.
+    /// The new socket is a copy of this one, with its own independent options
+    #[cfg(target_os = "linux")]
+    pub fn peeloff(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<SctpSocket> {
+        let sock = SctpSocket::from_sock(sctp_syscall!(sctp_peeloff(self.fd, assoc))?);
+        sock.prefer_modern.store(
+            self.prefer_modern.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        // Don't leak the socket into child processes
+        sock.set_cloexec(true)?;
+        Ok(sock)
+    }
+
     /// Accept connection to this socket
     pub fn accept(&self) -> Result<(SctpSocket, SocketAddr)> {
         // prepare buffer to store client address
@@ -311,25 +569,50 @@ impl SctpSocket {
         let mut addr_storage_length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
 
         let stream = {
-            syscall!(accept(
-                self.0,
+            syscall!(accept4(
+                self.fd,
                 addr_storage.as_mut_ptr() as *mut _,
-                &mut addr_storage_length
+                &mut addr_storage_length,
+                libc::SOCK_CLOEXEC
             ))
-            .map(SctpSocket)
+            .map(SctpSocket::from_sock)
         }?;
 
         unsafe { to_socket_addr(addr_storage.as_ptr()) }.map(|addr| (stream, addr))
     }
 
+    /// Get the primary local address of the socket (`getsockname`)
+    pub fn sockname(&self) -> Result<SocketAddr> {
+        let mut addr_storage: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::zeroed();
+        let mut len = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        syscall!(getsockname(
+            self.fd,
+            addr_storage.as_mut_ptr() as *mut _,
+            &mut len
+        ))?;
+        unsafe { to_socket_addr(addr_storage.as_ptr()) }
+    }
+
+    /// Get the primary address of the connected peer (`getpeername`)
+    pub fn peername(&self) -> Result<SocketAddr> {
+        let mut addr_storage: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::zeroed();
+        let mut len = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        syscall!(getpeername(
+            self.fd,
+            addr_storage.as_mut_ptr() as *mut _,
+            &mut len
+        ))?;
+        unsafe { to_socket_addr(addr_storage.as_ptr()) }
+    }
+
     fn addrs(&self, id: sctp_sys::sctp_assoc_t, what: SctpAddrType) -> Result<Vec<SocketAddr>> {
         unsafe {
             // Initialize a pointer that will hold the addresses
             let mut addrs: *mut sockaddr = std::ptr::null_mut();
-            let len = what.get(self.0, id, &mut addrs);
+            let len = what.get(self.fd, id, &mut addrs);
 
             if len < 0 {
-                return Err(Error::new(ErrorKind::Other, "Cannot retrieve addresses"));
+                return Err(Error::other("Cannot retrieve addresses"));
             }
             if len == 0 {
                 return Err(Error::new(ErrorKind::AddrNotAvailable, "Socket is unbound"));
@@ -346,10 +629,10 @@ impl SctpSocket {
                     AF_INET6 => size_of::<sockaddr_in6>() as socklen_t,
                     _ => {
                         what.free(addrs);
-                        return Err(Error::new(
-                            ErrorKind::Other,
-                            format!("Unsupported address family : {}", family),
-                        ));
+                        return Err(Error::other(format!(
+                            "Unsupported address family : {}",
+                            family
+                        )));
                     }
                 };
 
@@ -379,18 +662,47 @@ impl SctpSocket {
     pub fn recv(&mut self, buf: &mut [u8]) -> Result<usize> {
         let len = buf.len() as RWlen;
 
-        match syscall!(recv(self.0, buf.as_mut_ptr() as *mut libc::c_void, len, 0)) {
+        match syscall!(recv(self.fd, buf.as_mut_ptr() as *mut libc::c_void, len, 0)) {
             Err(err) => Err(err),
             Ok(recvlen) => Ok(recvlen as usize),
         }
     }
 
+    /// Report a blocking send which ran out of the time set with `SO_SNDTIMEO` as
+    /// `ErrorKind::TimedOut`, rather than the `EAGAIN` returned by the kernel.
+    /// `flags` are the `send(2)` flags of the call, which may have made it non-blocking
+    fn send_error(&self, err: Error, flags: libc::c_int) -> Error {
+        #[cfg(target_os = "linux")]
+        if err.kind() == ErrorKind::WouldBlock
+            && flags & libc::MSG_DONTWAIT == 0
+            && !self.is_nonblocking().unwrap_or(true)
+        {
+            return Error::new(
+                ErrorKind::TimedOut,
+                "Timed out waiting for room in the send buffer",
+            );
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = flags;
+        err
+    }
+
     /// Send data in TCP style. Only wmmatorks for a connected one to one socket
     pub fn send(&mut self, buf: &[u8]) -> Result<usize> {
+        self.send_flags(buf, 0)
+    }
+
+    /// Same as `send`, with the `send(2)` flags `flags` (e.g. `MSG_MORE`)
+    pub fn send_flags(&self, buf: &[u8], flags: libc::c_int) -> Result<usize> {
         let len = buf.len() as RWlen;
 
-        match syscall!(send(self.0, buf.as_ptr() as *const libc::c_void, len, 0)) {
-            Err(err) => Err(err),
+        match syscall!(send(
+            self.fd,
+            buf.as_ptr() as *const libc::c_void,
+            len,
+            flags
+        )) {
+            Err(err) => Err(self.send_error(err, flags)),
             Ok(recvlen) => Ok(recvlen as usize),
         }
     }
@@ -399,6 +711,57 @@ impl SctpSocket {
     /// the quantity of bytes received, the sctp stream id on which data were received, and
     /// the socket address used by the peer to send the data
     pub fn recvmsg(&self, msg: &mut [u8]) -> Result<(usize, u16, SocketAddr)> {
+        let (size, stream, addr, _) = self.recvmsg_with_flags(msg)?;
+        Ok((size, stream, addr))
+    }
+
+    /// Same as `recvmsg`, also returning the raw flags set by `sctp_recvmsg`
+    /// (`MSG_NOTIFICATION`, `MSG_EOR`, ...)
+    pub fn recvmsg_with_flags(
+        &self,
+        msg: &mut [u8],
+    ) -> Result<(usize, u16, SocketAddr, libc::c_int)> {
+        let info = self.recvmsg_info(msg)?;
+        Ok((info.bytes, info.stream, info.addr, info.flags))
+    }
+
+    /// Wait for data to be received, and return it along with its stream, payload protocol
+    /// identifier, flags, association and sender address. Both the `SCTP_SNDRCV` and
+    /// `SCTP_RCVINFO` ancillary data are decoded
+    #[cfg(target_os = "linux")]
+    pub fn recvmsg_info(&self, msg: &mut [u8]) -> Result<RecvInfo> {
+        RecvInfo::from_received(self.recv_full(msg)?)
+    }
+
+    /// Receive up to `bufs.len()` messages, waiting for at most `timeout` if not `None` for
+    /// the first one, then reading the following ones only while they are immediately
+    /// available. The information about `bufs[i]` is pushed to `infos` at index `i`, after
+    /// clearing it. Returns the number of messages received, 0 on timeout
+    #[cfg(target_os = "linux")]
+    pub fn recv_batch(
+        &self,
+        bufs: &mut [&mut [u8]],
+        infos: &mut Vec<RecvInfo>,
+        timeout: Option<Duration>,
+    ) -> Result<usize> {
+        infos.clear();
+        if bufs.is_empty() || !self.wait_readable_timeout(timeout)? {
+            return Ok(0);
+        }
+        for buf in bufs.iter_mut() {
+            match self.recv_full_flags(buf, CONTROL_LEN, libc::MSG_DONTWAIT) {
+                Ok(received) => infos.push(RecvInfo::from_received(received)?),
+                Err(ref err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(infos.len())
+    }
+
+    /// Wait for data to be received, and return it along with its stream, payload protocol
+    /// identifier, flags, association and sender address
+    #[cfg(not(target_os = "linux"))]
+    pub fn recvmsg_info(&self, msg: &mut [u8]) -> Result<RecvInfo> {
         let len = msg.len() as libc::size_t;
 
         let mut flags: libc::c_int = 0;
@@ -410,7 +773,7 @@ impl SctpSocket {
         let mut addr_storage_length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
 
         let recvlen = sctp_syscall!(sctp_recvmsg(
-            self.0,
+            self.fd,
             msg.as_mut_ptr() as *mut _,
             len,
             addr_storage.as_mut_ptr() as *mut _,
@@ -419,12 +782,277 @@ impl SctpSocket {
             &mut flags
         ))?;
 
-        unsafe { to_socket_addr(addr_storage.as_ptr()) }
-            .map(|addr| (recvlen as usize, info.sinfo_stream, addr))
+        unsafe { to_socket_addr(addr_storage.as_ptr()) }.map(|addr| RecvInfo {
+            bytes: recvlen as usize,
+            stream: info.sinfo_stream,
+            ppid: u32::from_be(info.sinfo_ppid),
+            flags,
+            assoc_id: info.sinfo_assoc_id,
+            unordered: info.sinfo_flags & sctp_sys::SCTP_UNORDERED as u16 != 0,
+            context: info.sinfo_context,
+            addr,
+        })
     }
 
-    /// Send data in Sctp style, to the provided address (may be `None` if the socket is connected), on the stream `stream`, with the TTL `ttl`.
-    /// On success, returns the quantity on bytes sent
+    /// Receive a message along with all the ancillary information provided by the kernel
+    #[cfg(target_os = "linux")]
+    pub fn recv_full(&self, buf: &mut [u8]) -> Result<ReceivedMessage> {
+        self.recv_full_with_control(buf, CONTROL_LEN)
+    }
+
+    /// Same as `recv_full`, with a control buffer of `control_len` bytes to receive
+    /// ancillary data. Fails with `ErrorKind::InvalidData` if the ancillary data
+    /// didn't fit in the control buffer, in which case the message is lost
+    #[cfg(target_os = "linux")]
+    pub fn recv_full_with_control(
+        &self,
+        buf: &mut [u8],
+        control_len: usize,
+    ) -> Result<ReceivedMessage> {
+        self.recv_full_flags(buf, control_len, 0)
+    }
+
+    /// Same as `recv_full_with_control`, with the `recvmsg(2)` flags `flags`
+    /// (e.g. `MSG_PEEK`, `MSG_DONTWAIT`)
+    #[cfg(target_os = "linux")]
+    pub fn recv_full_flags(
+        &self,
+        buf: &mut [u8],
+        control_len: usize,
+        flags: libc::c_int,
+    ) -> Result<ReceivedMessage> {
+        self.drain_dropped(control_len, flags)?;
+        let received = self.recv_raw(buf, control_len, flags)?;
+        if flags & libc::MSG_PEEK == 0 && !received.is_notification {
+            self.check_recv_limit(&received, control_len, flags)?;
+        }
+        Ok(received)
+    }
+
+    /// Set the maximum size of received messages, 0 for no limit. When a message grows
+    /// past the limit, the rest of it is read and dropped, and the receive call fails with
+    /// `ErrorKind::InvalidData`. The parts of the message already returned are not withdrawn
+    #[cfg(target_os = "linux")]
+    pub fn set_max_recv_message_size(&self, bytes: usize) {
+        self.recv_limit.max.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Get the maximum size of received messages, 0 for no limit
+    #[cfg(target_os = "linux")]
+    pub fn max_recv_message_size(&self) -> usize {
+        self.recv_limit.max.load(Ordering::Relaxed)
+    }
+
+    /// Read and drop the rest of a message which exceeded the maximum size, until its end
+    #[cfg(target_os = "linux")]
+    fn drain_dropped(&self, control_len: usize, flags: libc::c_int) -> Result<()> {
+        let mut scratch = [0u8; 4096];
+        while self.recv_limit.dropping.load(Ordering::Relaxed) {
+            let received = self.recv_raw(&mut scratch, control_len, flags & !libc::MSG_PEEK)?;
+            // Stop at the end of the message, or at end of file
+            if received.end_of_record || received.bytes == 0 {
+                self.recv_limit.dropping.store(false, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Account for the data just received, and drop the rest of the message if it exceeds
+    /// the maximum size. With `MSG_DONTWAIT` in `flags`, the part of the message which isn't
+    /// received yet is dropped by the next receive calls
+    #[cfg(target_os = "linux")]
+    fn check_recv_limit(
+        &self,
+        received: &ReceivedMessage,
+        control_len: usize,
+        flags: libc::c_int,
+    ) -> Result<()> {
+        let max = self.recv_limit.max.load(Ordering::Relaxed);
+        if received.end_of_record {
+            let total = self.recv_limit.current.swap(0, Ordering::Relaxed) + received.bytes;
+            if max == 0 || total <= max {
+                return Ok(());
+            }
+        } else {
+            let total = self
+                .recv_limit
+                .current
+                .fetch_add(received.bytes, Ordering::Relaxed)
+                + received.bytes;
+            if max == 0 || total <= max {
+                return Ok(());
+            }
+            self.recv_limit.current.store(0, Ordering::Relaxed);
+            self.recv_limit.dropping.store(true, Ordering::Relaxed);
+            match self.drain_dropped(control_len, flags) {
+                Err(ref err) if err.kind() == ErrorKind::WouldBlock => {}
+                res => res?,
+            }
+        }
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            "Received message exceeds the maximum size",
+        ))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn recv_raw(
+        &self,
+        buf: &mut [u8],
+        control_len: usize,
+        flags: libc::c_int,
+    ) -> Result<ReceivedMessage> {
+        let mut addr_storage: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::zeroed();
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        // u64 vector to keep the control buffer aligned for cmsghdr
+        let mut control = vec![0u64; control_len.div_ceil(size_of::<u64>())];
+        let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+        msg.msg_name = addr_storage.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = control_len as _;
+
+        let recvlen = syscall!(recvmsg(self.fd, &mut msg, flags))?;
+        check_ctrunc(&msg)?;
+
+        let mut received = ReceivedMessage {
+            bytes: recvlen as usize,
+            flags: msg.msg_flags,
+            end_of_record: msg.msg_flags & libc::MSG_EOR != 0,
+            is_notification: msg.msg_flags & ffi::MSG_NOTIFICATION != 0,
+            ..Default::default()
+        };
+        if msg.msg_namelen > 0 {
+            received.addr = unsafe { to_socket_addr(addr_storage.as_ptr()) }.ok();
+        }
+        unsafe { received.parse_cmsgs(&msg) };
+        Ok(received)
+    }
+
+    /// Wait for the next notification, for at most `timeout` if not `None`. Returns `None`
+    /// on timeout. Data messages are never consumed, so this must be the only consumer of
+    /// the receive queue: when a data message is at its head, it is left there and this fails
+    /// with `ErrorKind::InvalidData`. Fails with `ErrorKind::UnexpectedEof` at end of file
+    #[cfg(target_os = "linux")]
+    pub fn recv_notification(&self, timeout: Option<Duration>) -> Result<Option<SctpNotification>> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        let mut header = [0u8; size_of::<ffi::sctp_notification_header>()];
+        loop {
+            let remaining = match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => Some(remaining),
+                    _ => return Ok(None),
+                },
+                None => None,
+            };
+            if !self.wait_readable_timeout(remaining)? {
+                return Ok(None);
+            }
+            let peeked = match self.recv_full_flags(
+                &mut header,
+                CONTROL_LEN,
+                libc::MSG_PEEK | libc::MSG_DONTWAIT,
+            ) {
+                Err(ref err) if err.kind() == ErrorKind::WouldBlock => continue,
+                res => res?,
+            };
+            if !peeked.is_notification {
+                if peeked.bytes == 0 {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "Socket reached end of file",
+                    ));
+                }
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "A data message is at the head of the receive queue",
+                ));
+            }
+            let mut notification = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                // The rest of a partially read notification stays at the head of the queue
+                let received = self.recv_full_flags(&mut buf, CONTROL_LEN, libc::MSG_DONTWAIT)?;
+                if !received.is_notification {
+                    return Err(Error::other(
+                        "Another consumer received from the socket while reading a notification",
+                    ));
+                }
+                notification.extend_from_slice(&buf[..received.bytes]);
+                if received.end_of_record {
+                    return SctpNotification::parse(&notification).map(Some);
+                }
+            }
+        }
+    }
+
+    /// Wait until the socket is readable, for at most `timeout` if not `None`.
+    /// Returns `false` on timeout
+    #[cfg(target_os = "linux")]
+    pub fn wait_readable_timeout(&self, timeout: Option<Duration>) -> Result<bool> {
+        self.wait_events(libc::POLLIN, timeout)
+    }
+
+    /// Wait until the socket is writable, for at most `timeout` if not `None`.
+    /// Returns `false` on timeout
+    #[cfg(target_os = "linux")]
+    pub fn wait_writable_timeout(&self, timeout: Option<Duration>) -> Result<bool> {
+        self.wait_events(libc::POLLOUT, timeout)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn wait_events(&self, events: libc::c_short, timeout: Option<Duration>) -> Result<bool> {
+        let mut fds = [libc::pollfd {
+            fd: self.fd,
+            events,
+            revents: 0,
+        }];
+        let timeout_ms = match timeout {
+            Some(t) => std::cmp::min(t.as_millis().max(1), libc::c_int::MAX as u128) as libc::c_int,
+            None => -1,
+        };
+        loop {
+            match syscall!(poll(
+                fds.as_mut_ptr(),
+                fds.len() as libc::nfds_t,
+                timeout_ms
+            )) {
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+                Ok(ready) => return Ok(ready > 0),
+            }
+        }
+    }
+
+    /// Enable or disable the `SCTP_RCVINFO` ancillary data on received messages
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_rcvinfo(&self, on: bool) -> Result<()> {
+        let val: libc::c_int = if on { 1 } else { 0 };
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_RECVRCVINFO, &val)
+    }
+
+    /// Enable or disable the `SCTP_NXTINFO` ancillary data on received messages
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_nxtinfo(&self, on: bool) -> Result<()> {
+        let val: libc::c_int = if on { 1 } else { 0 };
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_RECVNXTINFO, &val)
+    }
+
+    /// Enable or disable reception timestamps (`SO_TIMESTAMP`) on received messages
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_timestamp(&self, on: bool) -> Result<()> {
+        let val: libc::c_int = if on { 1 } else { 0 };
+        self.setsockopt(libc::SOL_SOCKET, libc::SO_TIMESTAMP, &val)
+    }
+
+    /// Send data in Sctp style, to the provided address (may be `None` if the socket is connected),
+    /// with the payload protocol identifier `ppid` (in host byte order), on the stream `stream`,
+    /// with the TTL `ttl`. On success, returns the quantity on bytes sent
     pub fn sendmsg<A: ToSocketAddrs>(
         &self,
         msg: &[u8],
@@ -433,71 +1061,923 @@ impl SctpSocket {
         stream: u16,
         ttl: libc::c_ulong,
     ) -> Result<usize> {
+        self.sendmsg_flags(msg, address, ppid, 0, stream, ttl)
+    }
+
+    /// Same as `sendmsg`, with the SCTP send flags `flags` (`SCTP_UNORDERED`, `SCTP_ADDR_OVER`, ...)
+    pub fn sendmsg_flags<A: ToSocketAddrs>(
+        &self,
+        msg: &[u8],
+        address: Option<A>,
+        ppid: u32,
+        flags: libc::c_int,
+        stream: u16,
+        ttl: libc::c_ulong,
+    ) -> Result<usize> {
+        let address = match address {
+            Some(a) => Some(SocketAddr::from_addr(a)?),
+            None => None,
+        };
+        let ppid = ppid.to_be();
+
+        // sctp_sndinfo can't carry a lifetime, those sends keep going through sctp_sendmsg
+        #[cfg(target_os = "linux")]
+        if ttl == 0 && self.prefer_modern.load(Ordering::Relaxed) {
+            let info = ffi::sctp_sndinfo {
+                snd_sid: stream,
+                snd_flags: flags as u16,
+                snd_ppid: ppid,
+                snd_context: 0,
+                snd_assoc_id: 0,
+            };
+            return self.send_cmsg(msg, address.as_ref(), ffi::SCTP_SNDINFO, &info);
+        }
+
         let len = msg.len() as libc::size_t;
-        let (raw_addr, addr_len) = match address {
-            Some(a) => {
-                let addrobj = SocketAddr::from_addr(a)?;
-                let (addr_c_struct, addr_c_struct_len) = socket_addr(&addrobj);
+        let raw = address.as_ref().map(socket_addr);
+        let (raw_addr, addr_len) = match raw {
+            Some((ref addr_c_struct, addr_c_struct_len)) => {
                 (addr_c_struct.as_ptr() as *mut sockaddr, addr_c_struct_len)
             }
             None => (std::ptr::null_mut(), 0),
         };
-        let ppid = ppid.to_be();
 
         match sctp_syscall!(sctp_sendmsg(
-            self.0,
+            self.fd,
             msg.as_ptr() as *const libc::c_void,
             len,
             raw_addr,
             addr_len,
+            // Already in network byte order. sctp-sys declares the uint32_t parameters as
+            // c_ulong: the value is only zero-extended, and read back as 32 bits by libsctp
             ppid as libc::c_ulong,
-            0,
+            flags as libc::c_ulong,
             stream,
             ttl,
             0
         )) {
-            Err(err) => Err(err),
+            Err(err) => Err(self.send_error(err, 0)),
             Ok(sendlen) => Ok(sendlen as usize),
         }
     }
 
-    /// Shuts down the read, write, or both halves of this connection
-    pub fn shutdown(&self, how: Shutdown) -> Result<()> {
-        let side = match how {
-            Shutdown::Read => SHUT_RD,
-            Shutdown::Write => SHUT_WR,
-            Shutdown::Both => SHUT_RDWR,
-        };
-        match syscall!(shutdown(self.0, side)) {
-            Err(err) => Err(err),
-            Ok(_) => Ok(()),
+    /// Choose whether `sendmsg` goes through `sendmsg(2)` with an `SCTP_SNDINFO` control
+    /// message (the default), or through the deprecated `sctp_sendmsg` function
+    #[cfg(target_os = "linux")]
+    pub fn prefer_modern_api(&self, modern: bool) {
+        self.prefer_modern.store(modern, Ordering::Relaxed);
+    }
+
+    /// Send data on the association, stream and with the flags described in `info`.
+    /// On success, returns the quantity on bytes sent
+    pub fn send_info(&self, msg: &[u8], info: &sctp_sys::sctp_sndrcvinfo) -> Result<usize> {
+        let len = msg.len() as libc::size_t;
+        match sctp_syscall!(sctp_send(
+            self.fd,
+            msg.as_ptr() as *const libc::c_void,
+            len,
+            info,
+            0
+        )) {
+            Err(err) => Err(self.send_error(err, 0)),
+            Ok(sendlen) => Ok(sendlen as usize),
         }
     }
 
-    /// Set socket option
-    pub fn setsockopt<T>(
+    /// Same as `send_info`, with the destination address `addr` passed along with the send
+    /// information. Combined with the `SCTP_ADDR_OVER` flag, it selects the path the message
+    /// is sent over. On success, returns the quantity on bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_info_to(
         &self,
-        level: libc::c_int,
-        optname: libc::c_int,
-        optval: &T,
-    ) -> Result<()> {
-        let optval_ptr = optval as *const T as *const libc::c_void;
-        let optlen = size_of::<T>() as socklen_t;
+        msg: &[u8],
+        addr: &SocketAddr,
+        info: &sctp_sys::sctp_sndrcvinfo,
+    ) -> Result<usize> {
+        self.send_cmsg(msg, Some(addr), ffi::SCTP_SNDRCV, info)
+    }
 
-        match syscall!(setsockopt(self.0, level, optname, optval_ptr, optlen)) {
-            Err(err) => Err(err),
-            Ok(_) => Ok(()),
-        }
+    /// Send `msg` to `addr` on the stream and with the flags described in `info`, creating
+    /// the association with the parameters `init` (`SCTP_INIT`) if it doesn't exist yet.
+    /// On success, returns the quantity on bytes sent
+    #[cfg(target_os = "linux")]
+    pub fn send_init(
+        &self,
+        msg: &[u8],
+        addr: &SocketAddr,
+        info: &ffi::sctp_sndinfo,
+        init: &ffi::sctp_initmsg,
+    ) -> Result<usize> {
+        self.send_cmsgs(
+            msg,
+            Some(addr),
+            &[
+                (ffi::SCTP_INIT, struct_bytes(init)),
+                (ffi::SCTP_SNDINFO, struct_bytes(info)),
+            ],
+            0,
+        )
     }
 
-    /// Get socket option
-    pub fn getsockopt<T>(&self, level: libc::c_int, optname: libc::c_int) -> Result<T> {
-        let mut val: T = unsafe { std::mem::zeroed() };
+    /// Send `msg` on the stream `stream` of the connected peer with `MSG_ZEROCOPY`.
+    /// Zero-copy must have been enabled with `set_zerocopy`
+    #[cfg(target_os = "linux")]
+    pub fn send_zerocopy(&self, msg: &[u8], stream: u16) -> Result<usize> {
+        let info = ffi::sctp_sndinfo {
+            snd_sid: stream,
+            snd_flags: 0,
+            snd_ppid: 0,
+            snd_context: 0,
+            snd_assoc_id: 0,
+        };
+        self.send_cmsgs(
+            msg,
+            None,
+            &[(ffi::SCTP_SNDINFO, struct_bytes(&info))],
+            libc::MSG_ZEROCOPY,
+        )
+    }
+
+    /// Enable or disable zero-copy sends (`SO_ZEROCOPY`). Fails with
+    /// `ErrorKind::Unsupported` if the kernel doesn't support zero-copy on SCTP sockets
+    #[cfg(target_os = "linux")]
+    pub fn set_zerocopy(&self, on: bool) -> Result<()> {
+        let val: libc::c_int = if on { 1 } else { 0 };
+        match self.setsockopt(libc::SOL_SOCKET, libc::SO_ZEROCOPY, &val) {
+            Err(ref err)
+                if matches!(
+                    err.raw_os_error(),
+                    Some(libc::EOPNOTSUPP) | Some(ffi::ENOTSUPP)
+                ) =>
+            {
+                Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "Zero-copy sends are not supported on SCTP sockets by this kernel",
+                ))
+            }
+            res => res,
+        }
+    }
+
+    /// Send `msg` to `addr`, or to the connected peer if `None`, along with a single
+    /// `SOL_SCTP` control message of type `cmsg_type` holding `data`
+    #[cfg(target_os = "linux")]
+    fn send_cmsg<T: Copy>(
+        &self,
+        msg: &[u8],
+        addr: Option<&SocketAddr>,
+        cmsg_type: libc::c_int,
+        data: &T,
+    ) -> Result<usize> {
+        self.send_cmsgs(msg, addr, &[(cmsg_type, struct_bytes(data))], 0)
+    }
+
+    /// Send `msg` to `addr`, or to the connected peer if `None`, along with the `SOL_SCTP`
+    /// control messages `cmsgs`, given as their type and raw data, and the `sendmsg(2)`
+    /// flags `flags`
+    #[cfg(target_os = "linux")]
+    fn send_cmsgs(
+        &self,
+        msg: &[u8],
+        addr: Option<&SocketAddr>,
+        cmsgs: &[(libc::c_int, &[u8])],
+        flags: libc::c_int,
+    ) -> Result<usize> {
+        let raw = addr.map(socket_addr);
+        let mut iov = libc::iovec {
+            iov_base: msg.as_ptr() as *mut libc::c_void,
+            iov_len: msg.len(),
+        };
+        let space: usize = cmsgs
+            .iter()
+            .map(|(_, data)| unsafe { libc::CMSG_SPACE(data.len() as u32) } as usize)
+            .sum();
+        // u64 vector to keep the control buffer aligned for cmsghdr
+        let mut control = vec![0u64; space.div_ceil(size_of::<u64>())];
+        let mut hdr: libc::msghdr = unsafe { std::mem::zeroed() };
+        if let Some((ref raw_addr, raw_addr_length)) = raw {
+            hdr.msg_name = raw_addr.as_ptr() as *mut libc::c_void;
+            hdr.msg_namelen = raw_addr_length;
+        }
+        hdr.msg_iov = &mut iov;
+        hdr.msg_iovlen = 1;
+        hdr.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        hdr.msg_controllen = space as _;
+        unsafe {
+            let mut cmsg = libc::CMSG_FIRSTHDR(&hdr);
+            for (cmsg_type, data) in cmsgs {
+                (*cmsg).cmsg_level = sctp_sys::SOL_SCTP;
+                (*cmsg).cmsg_type = *cmsg_type;
+                (*cmsg).cmsg_len = libc::CMSG_LEN(data.len() as u32) as _;
+                std::ptr::copy_nonoverlapping(data.as_ptr(), libc::CMSG_DATA(cmsg), data.len());
+                cmsg = libc::CMSG_NXTHDR(&hdr, cmsg);
+            }
+        }
+        let sendlen =
+            syscall!(sendmsg(self.fd, &hdr, flags)).map_err(|e| self.send_error(e, flags))?;
+        Ok(sendlen as usize)
+    }
+
+    /// Receive up to `bufs.len()` messages with a single `recvmmsg(2)` call, with the
+    /// `recvmmsg` flags `flags` (e.g. `MSG_WAITFORONE`, `MSG_DONTWAIT`). Each entry holds one
+    /// SCTP message, or a part of it if it didn't fit in its buffer (`end_of_record` unset).
+    /// Returns one `ReceivedMessage` per filled buffer, in the order of `bufs`.
+    /// The maximum message size set with `set_max_recv_message_size` is not enforced
+    #[cfg(target_os = "linux")]
+    pub fn recvmmsg(
+        &self,
+        bufs: &mut [&mut [u8]],
+        flags: libc::c_int,
+    ) -> Result<Vec<ReceivedMessage>> {
+        let count = bufs.len();
+        let mut addrs: Vec<libc::sockaddr_storage> = vec![unsafe { std::mem::zeroed() }; count];
+        let mut iovs: Vec<libc::iovec> = bufs
+            .iter_mut()
+            .map(|buf| libc::iovec {
+                iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+                iov_len: buf.len(),
+            })
+            .collect();
+        // u64 vectors to keep the control buffers aligned for cmsghdr
+        let control_words = CONTROL_LEN.div_ceil(size_of::<u64>());
+        let mut controls = vec![0u64; control_words * count];
+        let mut hdrs: Vec<libc::mmsghdr> = (0..count)
+            .map(|i| {
+                let mut hdr: libc::mmsghdr = unsafe { std::mem::zeroed() };
+                hdr.msg_hdr.msg_name = &mut addrs[i] as *mut _ as *mut libc::c_void;
+                hdr.msg_hdr.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+                hdr.msg_hdr.msg_iov = &mut iovs[i];
+                hdr.msg_hdr.msg_iovlen = 1;
+                hdr.msg_hdr.msg_control =
+                    controls[i * control_words..].as_mut_ptr() as *mut libc::c_void;
+                hdr.msg_hdr.msg_controllen = CONTROL_LEN as _;
+                hdr
+            })
+            .collect();
+
+        let received = syscall!(recvmmsg(
+            self.fd,
+            hdrs.as_mut_ptr(),
+            count as libc::c_uint,
+            flags as _,
+            std::ptr::null_mut()
+        ))?;
+
+        let mut messages = Vec::with_capacity(received as usize);
+        for (i, hdr) in hdrs.iter().take(received as usize).enumerate() {
+            check_ctrunc(&hdr.msg_hdr)?;
+            let msg_flags = hdr.msg_hdr.msg_flags;
+            let mut message = ReceivedMessage {
+                bytes: hdr.msg_len as usize,
+                flags: msg_flags,
+                end_of_record: msg_flags & libc::MSG_EOR != 0,
+                is_notification: msg_flags & ffi::MSG_NOTIFICATION != 0,
+                ..Default::default()
+            };
+            if hdr.msg_hdr.msg_namelen > 0 {
+                message.addr = unsafe { to_socket_addr(&addrs[i]) }.ok();
+            }
+            unsafe { message.parse_cmsgs(&hdr.msg_hdr) };
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+
+    /// Send several messages with a single `sendmmsg(2)` call, with the `send(2)` flags
+    /// `flags`. Each entry is sent as one SCTP message, on its own stream and to its own
+    /// destination. Returns the quantity of bytes sent for each message actually sent,
+    /// which may be fewer than `msgs.len()`
+    #[cfg(target_os = "linux")]
+    pub fn sendmmsg(&self, msgs: &[BatchMessage<'_>], flags: libc::c_int) -> Result<Vec<usize>> {
+        let count = msgs.len();
+        let raw_addrs: Vec<_> = msgs
+            .iter()
+            .map(|msg| msg.addr.as_ref().map(socket_addr))
+            .collect();
+        let mut iovs: Vec<libc::iovec> = msgs
+            .iter()
+            .map(|msg| libc::iovec {
+                iov_base: msg.data.as_ptr() as *mut libc::c_void,
+                iov_len: msg.data.len(),
+            })
+            .collect();
+        let space = unsafe { libc::CMSG_SPACE(size_of::<ffi::sctp_sndinfo>() as u32) } as usize;
+        // u64 vectors to keep the control buffers aligned for cmsghdr
+        let control_words = space.div_ceil(size_of::<u64>());
+        let mut controls = vec![0u64; control_words * count];
+        let mut hdrs: Vec<libc::mmsghdr> = Vec::with_capacity(count);
+        for (i, msg) in msgs.iter().enumerate() {
+            let mut hdr: libc::mmsghdr = unsafe { std::mem::zeroed() };
+            if let Some((ref raw_addr, raw_addr_length)) = raw_addrs[i] {
+                hdr.msg_hdr.msg_name = raw_addr.as_ptr() as *mut libc::c_void;
+                hdr.msg_hdr.msg_namelen = raw_addr_length;
+            }
+            hdr.msg_hdr.msg_iov = &mut iovs[i];
+            hdr.msg_hdr.msg_iovlen = 1;
+            hdr.msg_hdr.msg_control =
+                controls[i * control_words..].as_mut_ptr() as *mut libc::c_void;
+            hdr.msg_hdr.msg_controllen = space as _;
+            let info = ffi::sctp_sndinfo {
+                snd_sid: msg.stream,
+                snd_flags: 0,
+                snd_ppid: msg.ppid.to_be(),
+                snd_context: 0,
+                snd_assoc_id: 0,
+            };
+            unsafe {
+                let cmsg = libc::CMSG_FIRSTHDR(&hdr.msg_hdr);
+                (*cmsg).cmsg_level = sctp_sys::SOL_SCTP;
+                (*cmsg).cmsg_type = ffi::SCTP_SNDINFO;
+                (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<ffi::sctp_sndinfo>() as u32) as _;
+                std::ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut ffi::sctp_sndinfo, info);
+            }
+            hdrs.push(hdr);
+        }
+
+        let sent = syscall!(sendmmsg(
+            self.fd,
+            hdrs.as_mut_ptr(),
+            count as libc::c_uint,
+            flags as _
+        ))
+        .map_err(|e| self.send_error(e, flags))?;
+        Ok(hdrs
+            .iter()
+            .take(sent as usize)
+            .map(|hdr| hdr.msg_len as usize)
+            .collect())
+    }
+
+    /// Get the memory used by the data in the send buffer (`SO_MEMINFO`). Chunks stay in the
+    /// send buffer until they're acknowledged by the peer, so this only drops to 0 once all
+    /// the data sent so far has been acknowledged
+    #[cfg(target_os = "linux")]
+    pub fn send_queued(&self) -> Result<u32> {
+        let meminfo: [u32; libc::SK_MEMINFO_WMEM_QUEUED as usize + 1] =
+            self.getsockopt(libc::SOL_SOCKET, libc::SO_MEMINFO)?;
+        Ok(meminfo[libc::SK_MEMINFO_WMEM_QUEUED as usize])
+    }
+
+    /// Get the status of the association `assoc`. On one-to-one sockets, `assoc` is ignored
+    #[cfg(target_os = "linux")]
+    pub fn status(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<SctpStatus> {
+        let raw: ffi::sctp_status = self.sctp_opt_info(sctp_sys::SCTP_STATUS, assoc)?;
+        Ok(SctpStatus::from_raw(&raw))
+    }
+
+    /// Get the ids of the associations of a one-to-many socket (`SCTP_GET_ASSOC_ID_LIST`)
+    #[cfg(target_os = "linux")]
+    pub fn assoc_ids(&self) -> Result<Vec<sctp_sys::sctp_assoc_t>> {
+        let count: u32 = self.getsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_GET_ASSOC_NUMBER)?;
+        // Leave room for associations set up in the meantime
+        let max = count as usize + 16;
+        let id_size = size_of::<sctp_sys::sctp_assoc_t>();
+        let buf = self.getsockopt_bytes(
+            sctp_sys::SOL_SCTP,
+            sctp_sys::SCTP_GET_ASSOC_ID_LIST,
+            size_of::<u32>() + max * id_size,
+        )?;
+        if buf.len() < size_of::<u32>() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Truncated SCTP_GET_ASSOC_ID_LIST value",
+            ));
+        }
+        let num = u32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+        Ok(buf[size_of::<u32>()..]
+            .chunks_exact(id_size)
+            .take(num)
+            .map(|c| sctp_sys::sctp_assoc_t::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+            .collect())
+    }
+
+    /// Get the association parameters (`SCTP_ASSOCINFO`)
+    #[cfg(target_os = "linux")]
+    pub fn associnfo(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<ffi::sctp_assocparams> {
+        self.sctp_opt_info(sctp_sys::SCTP_ASSOCINFO, assoc)
+    }
+
+    /// Subscribe to the SCTP events enabled in `events`
+    #[cfg(target_os = "linux")]
+    pub fn subscribe_events(&self, events: &EventSubscription) -> Result<()> {
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_EVENTS, &events.to_raw())
+    }
+
+    /// Get the SCTP events this socket is currently subscribed to
+    #[cfg(target_os = "linux")]
+    pub fn events(&self) -> Result<EventSubscription> {
+        let raw: ffi::sctp_event_subscribe =
+            self.getsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_EVENTS)?;
+        Ok(EventSubscription::from_raw(&raw))
+    }
+
+    /// Get the parameters used when initiating new associations (`SCTP_INITMSG`)
+    #[cfg(target_os = "linux")]
+    pub fn initmsg(&self) -> Result<ffi::sctp_initmsg> {
+        self.getsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_INITMSG)
+    }
+
+    /// Set the parameters used when initiating new associations (`SCTP_INITMSG`).
+    /// Zero values leave the corresponding kernel default untouched.
+    /// Has no effect on already established associations
+    #[cfg(target_os = "linux")]
+    pub fn set_initmsg(
+        &self,
+        out_streams: u16,
+        in_streams: u16,
+        max_attempts: u16,
+        max_init_timeo: u16,
+    ) -> Result<()> {
+        let initmsg = ffi::sctp_initmsg {
+            sinit_num_ostreams: out_streams,
+            sinit_max_instreams: in_streams,
+            sinit_max_attempts: max_attempts,
+            sinit_max_init_timeo: max_init_timeo,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_INITMSG, &initmsg)
+    }
+
+    /// Set how many times INIT is retransmitted before giving up on a new association
+    /// (`sinit_max_attempts`), keeping the other `SCTP_INITMSG` parameters unchanged
+    #[cfg(target_os = "linux")]
+    pub fn set_max_init_attempts(&self, attempts: u16) -> Result<()> {
+        let mut initmsg = self.initmsg()?;
+        initmsg.sinit_max_attempts = attempts;
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_INITMSG, &initmsg)
+    }
+
+    /// Set the number of retransmissions after which the path to `address` is considered
+    /// as failed (`spp_pathmaxrxt`), triggering a failover to another peer address
+    #[cfg(target_os = "linux")]
+    pub fn set_path_max_retransmits<A: ToSocketAddrs>(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        address: A,
+        count: u16,
+    ) -> Result<()> {
+        if count == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Path max retransmissions must be greater than 0",
+            ));
+        }
+        let addrobj = SocketAddr::from_addr(address)?;
+        let mut params: ffi::sctp_paddrparams = unsafe { std::mem::zeroed() };
+        params.spp_assoc_id = assoc;
+        params.spp_address = sockaddr_storage(&addrobj);
+        params.spp_pathmaxrxt = count;
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_PEER_ADDR_PARAMS, &params)
+    }
+
+    /// Set the peer address used as primary destination (`SCTP_PRIMARY_ADDR`)
+    #[cfg(target_os = "linux")]
+    pub fn set_primary_addr(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        address: &SocketAddr,
+    ) -> Result<()> {
+        let prim = ffi::sctp_prim {
+            ssp_assoc_id: assoc,
+            ssp_addr: sockaddr_storage(address),
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_PRIMARY_ADDR, &prim)
+    }
+
+    /// Get the information about the path to the peer address `address` of the
+    /// association `assoc` (`SCTP_GET_PEER_ADDR_INFO`)
+    #[cfg(target_os = "linux")]
+    pub fn peer_addr_info(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        address: &SocketAddr,
+    ) -> Result<ffi::sctp_paddrinfo> {
+        let mut info: ffi::sctp_paddrinfo = unsafe { std::mem::zeroed() };
+        info.spinfo_address = sockaddr_storage(address);
+        self.sctp_opt_info_with(sctp_sys::SCTP_GET_PEER_ADDR_INFO, assoc, info)
+    }
+
+    /// Get the smallest path MTU among all the peer addresses of the association `assoc`
+    #[cfg(target_os = "linux")]
+    pub fn min_path_mtu(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<u32> {
+        let mut min = None;
+        for addr in self.peer_addrs(assoc)? {
+            let mtu = self.peer_addr_info(assoc, &addr)?.spinfo_mtu;
+            min = Some(min.map_or(mtu, |m: u32| m.min(mtu)));
+        }
+        min.ok_or_else(|| Error::new(ErrorKind::NotConnected, "Association has no peer address"))
+    }
+
+    /// Set the parameters of the path to the peer address `address` of the association
+    /// `assoc`, or the default parameters of the association if `None`
+    /// (`SCTP_PEER_ADDR_PARAMS`)
+    #[cfg(target_os = "linux")]
+    pub fn set_peer_addr_params(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        address: Option<&SocketAddr>,
+        params: &PeerAddrParams,
+    ) -> Result<()> {
+        let mut raw: ffi::sctp_paddrparams = unsafe { std::mem::zeroed() };
+        raw.spp_assoc_id = assoc;
+        if let Some(address) = address {
+            raw.spp_address = sockaddr_storage(address);
+        }
+        params.fill_raw(&mut raw);
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_PEER_ADDR_PARAMS, &raw)
+    }
+
+    /// Get the parameters of the path to the peer address `address` of the association
+    /// `assoc`, or the default parameters of the association if `None`
+    #[cfg(target_os = "linux")]
+    pub fn peer_addr_params(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        address: Option<&SocketAddr>,
+    ) -> Result<PeerAddrParams> {
+        let mut raw: ffi::sctp_paddrparams = unsafe { std::mem::zeroed() };
+        if let Some(address) = address {
+            raw.spp_address = sockaddr_storage(address);
+        }
+        let raw = self.sctp_opt_info_with(sctp_sys::SCTP_PEER_ADDR_PARAMS, assoc, raw)?;
+        Ok(PeerAddrParams::from_raw(&raw))
+    }
+
+    /// Request an immediate heartbeat on the path to `address` (`SPP_HB_DEMAND`)
+    #[cfg(target_os = "linux")]
+    pub fn request_heartbeat(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        address: &SocketAddr,
+    ) -> Result<()> {
+        let mut params: ffi::sctp_paddrparams = unsafe { std::mem::zeroed() };
+        params.spp_assoc_id = assoc;
+        params.spp_address = sockaddr_storage(address);
+        params.spp_flags = ffi::SPP_HB_DEMAND;
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_PEER_ADDR_PARAMS, &params)
+    }
+
+    /// Enable or disable support of the stream reconfiguration extension (RFC 6525) for new
+    /// associations (`SCTP_RECONFIG_SUPPORTED`)
+    #[cfg(target_os = "linux")]
+    pub fn set_reconfig_supported(&self, on: bool) -> Result<()> {
+        let val = ffi::sctp_assoc_value {
+            assoc_id: 0,
+            assoc_value: on as u32,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, ffi::SCTP_RECONFIG_SUPPORTED, &val)
+    }
+
+    /// Check if the stream reconfiguration extension was negotiated with the peer of the
+    /// association `assoc` (`SCTP_RECONFIG_SUPPORTED`)
+    #[cfg(target_os = "linux")]
+    pub fn reconfig_supported(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<bool> {
+        let val: ffi::sctp_assoc_value = self.sctp_opt_info(ffi::SCTP_RECONFIG_SUPPORTED, assoc)?;
+        Ok(val.assoc_value != 0)
+    }
+
+    /// Set which stream reconfiguration requests are enabled (`SCTP_ENABLE_STREAM_RESET`),
+    /// as a combination of the `SCTP_ENABLE_*_REQ` flags
+    #[cfg(target_os = "linux")]
+    pub fn set_enable_stream_reset(&self, assoc: sctp_sys::sctp_assoc_t, flags: u32) -> Result<()> {
+        let val = ffi::sctp_assoc_value {
+            assoc_id: assoc,
+            assoc_value: flags,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, ffi::SCTP_ENABLE_STREAM_RESET, &val)
+    }
+
+    /// Get which stream reconfiguration requests are enabled (`SCTP_ENABLE_STREAM_RESET`)
+    #[cfg(target_os = "linux")]
+    pub fn enable_stream_reset(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<u32> {
+        let val: ffi::sctp_assoc_value =
+            self.sctp_opt_info(ffi::SCTP_ENABLE_STREAM_RESET, assoc)?;
+        Ok(val.assoc_value)
+    }
+
+    /// Fail with a clear error if the association `assoc` can't send the stream
+    /// reconfiguration request enabled by `flag`
+    #[cfg(target_os = "linux")]
+    fn check_stream_reset(&self, assoc: sctp_sys::sctp_assoc_t, flag: u32) -> Result<()> {
+        if !self.reconfig_supported(assoc)? || self.enable_stream_reset(assoc)? & flag == 0 {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "stream reset not negotiated; enable via builder before connect",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reset the streams `streams` of the association `assoc`, in the directions given by
+    /// `flags` (`SCTP_STREAM_RESET_INCOMING` and/or `SCTP_STREAM_RESET_OUTGOING`).
+    /// An empty list resets all the streams
+    #[cfg(target_os = "linux")]
+    pub fn reset_streams(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        streams: &[u16],
+        flags: u16,
+    ) -> Result<()> {
+        self.check_stream_reset(assoc, ffi::SCTP_ENABLE_RESET_STREAM_REQ)?;
+        let count = u16::try_from(streams.len())
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "Too many streams"))?;
+        // struct sctp_reset_streams, ending with a flexible array of stream ids
+        let mut buf =
+            Vec::with_capacity(size_of::<sctp_sys::sctp_assoc_t>() + 4 + 2 * streams.len());
+        buf.extend_from_slice(&assoc.to_ne_bytes());
+        buf.extend_from_slice(&flags.to_ne_bytes());
+        buf.extend_from_slice(&count.to_ne_bytes());
+        for stream in streams {
+            buf.extend_from_slice(&stream.to_ne_bytes());
+        }
+        self.setsockopt_bytes(sctp_sys::SOL_SCTP, ffi::SCTP_RESET_STREAMS, &buf)
+    }
+
+    /// Add `in_streams` inbound and `out_streams` outbound streams to the association `assoc`
+    #[cfg(target_os = "linux")]
+    pub fn add_streams(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        in_streams: u16,
+        out_streams: u16,
+    ) -> Result<()> {
+        self.check_stream_reset(assoc, ffi::SCTP_ENABLE_CHANGE_ASSOC_REQ)?;
+        let val = ffi::sctp_add_streams {
+            sas_assoc_id: assoc,
+            sas_instrms: in_streams,
+            sas_outstrms: out_streams,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, ffi::SCTP_ADD_STREAMS, &val)
+    }
+
+    /// Set the initial, maximum and minimum retransmission timeouts of the association
+    /// `assoc`, in milliseconds (`SCTP_RTOINFO`). Fails with `ErrorKind::InvalidInput`
+    /// unless `min <= initial <= max`
+    #[cfg(target_os = "linux")]
+    pub fn set_rto_info(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        initial: u32,
+        max: u32,
+        min: u32,
+    ) -> Result<()> {
+        if min > initial || initial > max {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "RTO values must satisfy min <= initial <= max",
+            ));
+        }
+        let info = ffi::sctp_rtoinfo {
+            srto_assoc_id: assoc,
+            srto_initial: initial,
+            srto_max: max,
+            srto_min: min,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_RTOINFO, &info)
+    }
+
+    /// Get the initial, maximum and minimum retransmission timeouts of the association
+    /// `assoc`, in milliseconds
+    #[cfg(target_os = "linux")]
+    pub fn rto_info(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<(u32, u32, u32)> {
+        let info: ffi::sctp_rtoinfo = self.sctp_opt_info(sctp_sys::SCTP_RTOINFO, assoc)?;
+        Ok((info.srto_initial, info.srto_max, info.srto_min))
+    }
+
+    /// Set the largest user message fragment put in a DATA chunk for the association `assoc`
+    /// (`SCTP_MAXSEG`), in bytes. A `size` of 0 restores the default, which follows the
+    /// path MTU
+    #[cfg(target_os = "linux")]
+    pub fn set_max_seg(&self, assoc: sctp_sys::sctp_assoc_t, size: u32) -> Result<()> {
+        let val = ffi::sctp_assoc_value {
+            assoc_id: assoc,
+            assoc_value: size,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_MAXSEG, &val)
+    }
+
+    /// Get the fragmentation point of the association `assoc` (`SCTP_MAXSEG`), in bytes.
+    /// For an existing association, this is the effective value. Otherwise, it's the value
+    /// set for new associations, 0 meaning the default
+    #[cfg(target_os = "linux")]
+    pub fn max_seg(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<u32> {
+        let val: ffi::sctp_assoc_value = self.sctp_opt_info(sctp_sys::SCTP_MAXSEG, assoc)?;
+        Ok(val.assoc_value)
+    }
+
+    /// Configure delayed SACKs of the association `assoc` (`SCTP_DELAYED_SACK`): a SACK is
+    /// sent after `freq` packets, or after `delay` if fewer packets were received.
+    /// The valid combinations are:
+    /// * a zero `delay` with `freq` 1, acknowledging every packet immediately
+    /// * a `delay` between 1 and 500 ms with `freq` 2 or more
+    ///
+    /// Other combinations are rejected with `ErrorKind::InvalidInput`, instead of being
+    /// partially or silently ignored by the kernel
+    #[cfg(target_os = "linux")]
+    pub fn set_delayed_sack(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        delay: Duration,
+        freq: u32,
+    ) -> Result<()> {
+        let invalid = |msg| Err(Error::new(ErrorKind::InvalidInput, msg));
+        let delay_ms = delay.as_millis();
+        if freq == 0 {
+            return invalid("SACK frequency must be at least 1");
+        }
+        if delay_ms > ffi::SCTP_MAX_SACK_DELAY as u128 {
+            return invalid("SACK delay must not exceed 500 ms");
+        }
+        if delay.is_zero() != (freq == 1) {
+            return invalid(
+                "Invalid SACK delay and frequency: use a zero delay with frequency 1 for \
+                 immediate acks, or a delay of at least 1 ms with frequency 2 or more",
+            );
+        }
+        if delay_ms == 0 && !delay.is_zero() {
+            return invalid("SACK delay must be at least 1 ms");
+        }
+        let info = ffi::sctp_sack_info {
+            sack_assoc_id: assoc,
+            sack_delay: delay_ms as u32,
+            sack_freq: freq,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_DELAYED_SACK, &info)
+    }
+
+    /// Get the delayed SACK delay and frequency of the association `assoc`.
+    /// A frequency of 1 means delayed SACKs are disabled
+    #[cfg(target_os = "linux")]
+    pub fn delayed_sack(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<(Duration, u32)> {
+        let info: ffi::sctp_sack_info = self.sctp_opt_info(sctp_sys::SCTP_DELAYED_SACK, assoc)?;
+        Ok((
+            Duration::from_millis(info.sack_delay as u64),
+            info.sack_freq,
+        ))
+    }
+
+    /// Select the congestion control algorithm of the association `assoc` by name
+    /// (`rfc2581`, `hstcp`, `htcp` or `rtcc`). Fails with `ErrorKind::Unsupported` if the
+    /// kernel doesn't allow selecting it per socket, which is the case of Linux
+    #[cfg(target_os = "linux")]
+    pub fn set_congestion_control(&self, assoc: sctp_sys::sctp_assoc_t, name: &str) -> Result<()> {
+        let cc = ffi::SCTP_CC_NAMES
+            .iter()
+            .position(|n| *n == name)
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unknown SCTP congestion control algorithm: {}", name),
+                )
+            })?;
+        let val = ffi::sctp_assoc_value {
+            assoc_id: assoc,
+            assoc_value: cc as u32,
+        };
+        cc_unsupported(self.setsockopt(sctp_sys::SOL_SCTP, ffi::SCTP_PLUGGABLE_CC, &val))
+    }
+
+    /// Get the name of the congestion control algorithm of the association `assoc`.
+    /// Fails with `ErrorKind::Unsupported` if the kernel doesn't expose it, which is the
+    /// case of Linux
+    #[cfg(target_os = "linux")]
+    pub fn congestion_control(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<String> {
+        let val: ffi::sctp_assoc_value =
+            cc_unsupported(self.sctp_opt_info(ffi::SCTP_PLUGGABLE_CC, assoc))?;
+        match ffi::SCTP_CC_NAMES.get(val.assoc_value as usize) {
+            Some(name) => Ok(name.to_string()),
+            None => Ok(format!("unknown({})", val.assoc_value)),
+        }
+    }
+
+    /// Select the stream scheduler of the association `assoc` (`SCTP_STREAM_SCHEDULER`),
+    /// as one of the `SCTP_SS_*` values
+    #[cfg(target_os = "linux")]
+    pub fn set_stream_scheduler(&self, assoc: sctp_sys::sctp_assoc_t, sched: u32) -> Result<()> {
+        let val = ffi::sctp_assoc_value {
+            assoc_id: assoc,
+            assoc_value: sched,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, ffi::SCTP_STREAM_SCHEDULER, &val)
+    }
+
+    /// Set the scheduler parameter of `stream` in the association `assoc`
+    /// (`SCTP_STREAM_SCHEDULER_VALUE`), e.g. its priority with the priority scheduler
+    #[cfg(target_os = "linux")]
+    pub fn set_stream_scheduler_value(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        stream: u16,
+        value: u16,
+    ) -> Result<()> {
+        let val = ffi::sctp_stream_value {
+            assoc_id: assoc,
+            stream_id: stream,
+            stream_value: value,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, ffi::SCTP_STREAM_SCHEDULER_VALUE, &val)
+    }
+
+    /// Get the statistics of the association `assoc` (`SCTP_GET_ASSOC_STATS`)
+    #[cfg(target_os = "linux")]
+    pub fn assoc_stats(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<ffi::sctp_assoc_stats> {
+        self.sctp_opt_info(sctp_sys::SCTP_GET_ASSOC_STATS, assoc)
+    }
+
+    /// Set the default partial reliability policy applied to messages sent without
+    /// an explicit policy (`SCTP_DEFAULT_PRINFO`)
+    #[cfg(target_os = "linux")]
+    pub fn set_default_prinfo(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        policy: PrPolicy,
+    ) -> Result<()> {
+        let (pr_policy, pr_value) = policy.to_raw()?;
+        let info = ffi::sctp_default_prinfo {
+            pr_assoc_id: assoc,
+            pr_value,
+            pr_policy,
+        };
+        self.setsockopt(sctp_sys::SOL_SCTP, ffi::SCTP_DEFAULT_PRINFO, &info)
+    }
+
+    /// Get the default partial reliability policy (`SCTP_DEFAULT_PRINFO`)
+    #[cfg(target_os = "linux")]
+    pub fn default_prinfo(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<PrPolicy> {
+        let info: ffi::sctp_default_prinfo = self.sctp_opt_info(ffi::SCTP_DEFAULT_PRINFO, assoc)?;
+        Ok(PrPolicy::from_raw(info.pr_policy, info.pr_value))
+    }
+
+    /// Set the stream and payload protocol identifier used by sends which don't specify
+    /// them (`SCTP_DEFAULT_SEND_PARAM`)
+    pub fn set_default_send_param(
+        &self,
+        assoc: sctp_sys::sctp_assoc_t,
+        stream: u16,
+        ppid: u32,
+    ) -> Result<()> {
+        let mut info: sctp_sys::sctp_sndrcvinfo = unsafe { std::mem::zeroed() };
+        info.sinfo_stream = stream;
+        info.sinfo_ppid = ppid.to_be();
+        info.sinfo_assoc_id = assoc;
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_DEFAULT_SEND_PARAM, &info)
+    }
+
+    /// Get the default stream and payload protocol identifier (`SCTP_DEFAULT_SEND_PARAM`)
+    pub fn default_send_param(&self, assoc: sctp_sys::sctp_assoc_t) -> Result<(u16, u32)> {
+        let info: sctp_sys::sctp_sndrcvinfo =
+            self.sctp_opt_info(sctp_sys::SCTP_DEFAULT_SEND_PARAM, assoc)?;
+        Ok((info.sinfo_stream, u32::from_be(info.sinfo_ppid)))
+    }
+
+    /// Set the idle time in seconds after which associations are automatically closed
+    /// (`SCTP_AUTOCLOSE`). 0 disables autoclose. Only applies to one-to-many sockets
+    pub fn set_autoclose(&self, seconds: u32) -> Result<()> {
+        self.setsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_AUTOCLOSE, &seconds)
+    }
+
+    /// Get the idle time in seconds after which associations are automatically closed.
+    /// 0 means autoclose is disabled
+    pub fn autoclose(&self) -> Result<u32> {
+        self.getsockopt(sctp_sys::SOL_SCTP, sctp_sys::SCTP_AUTOCLOSE)
+    }
+
+    /// Shuts down the read, write, or both halves of this connection
+    pub fn shutdown(&self, how: Shutdown) -> Result<()> {
+        let side = match how {
+            Shutdown::Read => SHUT_RD,
+            Shutdown::Write => SHUT_WR,
+            Shutdown::Both => SHUT_RDWR,
+        };
+        match syscall!(shutdown(self.fd, side)) {
+            Err(err) => Err(err),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// Set socket option
+    pub fn setsockopt<T>(
+        &self,
+        level: libc::c_int,
+        optname: libc::c_int,
+        optval: &T,
+    ) -> Result<()> {
+        let optval_ptr = optval as *const T as *const libc::c_void;
+        let optlen = size_of::<T>() as socklen_t;
+
+        match syscall!(setsockopt(self.fd, level, optname, optval_ptr, optlen)) {
+            Err(err) => Err(err),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// Get socket option
+    pub fn getsockopt<T>(&self, level: libc::c_int, optname: libc::c_int) -> Result<T> {
+        let mut val: T = unsafe { std::mem::zeroed() };
 
         let mut len = size_of::<T>() as socklen_t;
 
         match syscall!(getsockopt(
-            self.0,
+            self.fd,
             level,
             optname,
             &mut val as *mut T as *mut libc::c_void,
@@ -508,17 +1988,132 @@ impl SctpSocket {
         }
     }
 
+    /// Set a variable length socket option, from raw bytes
+    pub fn setsockopt_bytes(
+        &self,
+        level: libc::c_int,
+        optname: libc::c_int,
+        optval: &[u8],
+    ) -> Result<()> {
+        match syscall!(setsockopt(
+            self.fd,
+            level,
+            optname,
+            optval.as_ptr() as *const libc::c_void,
+            optval.len() as socklen_t
+        )) {
+            Err(err) => Err(err),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// Get a variable length socket option as raw bytes, reading at most `max_len` bytes
+    pub fn getsockopt_bytes(
+        &self,
+        level: libc::c_int,
+        optname: libc::c_int,
+        max_len: usize,
+    ) -> Result<Vec<u8>> {
+        let mut buf = vec![0u8; max_len];
+        let mut len = max_len as socklen_t;
+
+        match syscall!(getsockopt(
+            self.fd,
+            level,
+            optname,
+            buf.as_mut_ptr() as *mut libc::c_void,
+            &mut len
+        )) {
+            Err(err) => Err(err),
+            Ok(_) => {
+                buf.truncate(len as usize);
+                Ok(buf)
+            }
+        }
+    }
+
+    /// Set the HMAC algorithms allowed to authenticate state cookies (`SCTP_HMAC_IDENT`),
+    /// by order of preference. At least one of them must be supported by the kernel
+    #[cfg(target_os = "linux")]
+    pub fn set_hmac_ident(&self, algos: &[u16]) -> Result<()> {
+        if algos.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No HMAC algorithm given",
+            ));
+        }
+        // struct sctp_hmacalgo { __u32 shmac_num_idents; __u16 shmac_idents[]; }
+        let mut buf = Vec::with_capacity(size_of::<u32>() + std::mem::size_of_val(algos));
+        buf.extend_from_slice(&(algos.len() as u32).to_ne_bytes());
+        for algo in algos {
+            buf.extend_from_slice(&algo.to_ne_bytes());
+        }
+        self.setsockopt_bytes(sctp_sys::SOL_SCTP, sctp_sys::SCTP_HMAC_IDENT, &buf)
+    }
+
+    /// Get the HMAC algorithms allowed to authenticate state cookies, by order of preference
+    #[cfg(target_os = "linux")]
+    pub fn hmac_ident(&self) -> Result<Vec<u16>> {
+        let buf = self.getsockopt_bytes(sctp_sys::SOL_SCTP, sctp_sys::SCTP_HMAC_IDENT, 256)?;
+        if buf.len() < size_of::<u32>() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Truncated SCTP_HMAC_IDENT value",
+            ));
+        }
+        let num = u32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+        Ok(buf[size_of::<u32>()..]
+            .chunks_exact(size_of::<u16>())
+            .take(num)
+            .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+            .collect())
+    }
+
+    /// Check if the option `name` at level `level` is supported, by reading it.
+    /// Errors other than `ENOPROTOOPT` and permission errors (for instance, an option
+    /// requiring an established association) mean the kernel knows the option
+    #[cfg(target_os = "linux")]
+    pub fn probe_option(&self, level: libc::c_int, name: libc::c_int) -> OptionSupport {
+        match self.getsockopt_bytes(level, name, 256) {
+            Ok(_) => OptionSupport::Supported,
+            Err(ref err) if err.raw_os_error() == Some(libc::ENOPROTOOPT) => {
+                OptionSupport::NotSupported
+            }
+            Err(ref err) if err.kind() == ErrorKind::PermissionDenied => {
+                OptionSupport::PermissionDenied
+            }
+            Err(_) => OptionSupport::Supported,
+        }
+    }
+
+    /// Enable or disable kernel socket debug tracing (`SO_DEBUG`).
+    /// Requires the `CAP_NET_ADMIN` capability, and fails with `ErrorKind::PermissionDenied` otherwise
+    pub fn set_debug(&self, on: bool) -> Result<()> {
+        let val: libc::c_int = if on { 1 } else { 0 };
+        self.setsockopt(libc::SOL_SOCKET, libc::SO_DEBUG, &val)
+    }
+
     /// Get SCTP socket option
     pub fn sctp_opt_info<T>(
         &self,
         optname: libc::c_int,
         assoc: sctp_sys::sctp_assoc_t,
     ) -> Result<T> {
-        let mut val: T = unsafe { std::mem::zeroed() };
+        self.sctp_opt_info_with(optname, assoc, unsafe { std::mem::zeroed() })
+    }
+
+    /// Get SCTP socket option, passing `val` as input. Used by options which
+    /// take parameters other than the association id, like a peer address
+    pub fn sctp_opt_info_with<T>(
+        &self,
+        optname: libc::c_int,
+        assoc: sctp_sys::sctp_assoc_t,
+        mut val: T,
+    ) -> Result<T> {
         let mut len = size_of::<T>() as socklen_t;
 
         match sctp_syscall!(sctp_opt_info(
-            self.0,
+            self.fd,
             assoc,
             optname,
             &mut val as *mut T as *mut libc::c_void,
@@ -529,12 +2124,179 @@ impl SctpSocket {
         }
     }
 
+    /// Set or unset the close-on-exec flag (`FD_CLOEXEC`). Sockets are created with it set
+    #[cfg(target_os = "linux")]
+    pub fn set_cloexec(&self, cloexec: bool) -> Result<()> {
+        let flags = syscall!(fcntl(self.fd, libc::F_GETFD))?;
+        let new_flags = if cloexec {
+            flags | libc::FD_CLOEXEC
+        } else {
+            flags & !libc::FD_CLOEXEC
+        };
+        if new_flags != flags {
+            syscall!(fcntl(self.fd, libc::F_SETFD, new_flags))?;
+        }
+        Ok(())
+    }
+
+    /// Check if the socket is in non-blocking mode
+    #[cfg(target_os = "linux")]
+    pub fn is_nonblocking(&self) -> Result<bool> {
+        let flags = syscall!(fcntl(self.fd, libc::F_GETFL))?;
+        Ok(flags & libc::O_NONBLOCK != 0)
+    }
+
+    /// Move the socket into or out of non-blocking mode
+    #[cfg(target_os = "linux")]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        let flags = syscall!(fcntl(self.fd, libc::F_GETFL))?;
+        let new_flags = if nonblocking {
+            flags | libc::O_NONBLOCK
+        } else {
+            flags & !libc::O_NONBLOCK
+        };
+        if new_flags != flags {
+            syscall!(fcntl(self.fd, libc::F_SETFL, new_flags))?;
+        }
+        Ok(())
+    }
+
+    /// Move the socket into or out of non-blocking mode
+    #[cfg(target_os = "windows")]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        let mut mode: winapi::u_long = if nonblocking { 1 } else { 0 };
+        if unsafe { ws2_32::ioctlsocket(self.fd, winapi::FIONBIO, &mut mode) } != 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Set the timeout of the socket option `optname` (`SO_RCVTIMEO` or `SO_SNDTIMEO`).
+    /// `None` or a zero duration clear the timeout
+    pub fn set_timeout(&self, optname: libc::c_int, timeout: Option<Duration>) -> Result<()> {
+        let timeout = timeout.unwrap_or(Duration::ZERO);
+        // Round sub-microsecond timeouts up, instead of clearing the timeout
+        let micros = if timeout.subsec_micros() == 0 && timeout.subsec_nanos() != 0 {
+            1
+        } else {
+            timeout.subsec_micros()
+        };
+        // Workaround: Use of long instead of libc::time_t which does not compile in windows x86_64
+        let tval = libc::timeval {
+            tv_sec: timeout.as_secs() as libc::c_long,
+            tv_usec: micros as _,
+        };
+        self.setsockopt(libc::SOL_SOCKET, optname, &tval)
+    }
+
+    /// Get the timeout of the socket option `optname` (`SO_RCVTIMEO` or `SO_SNDTIMEO`).
+    /// `None` means there's no timeout
+    pub fn timeout(&self, optname: libc::c_int) -> Result<Option<Duration>> {
+        let tval: libc::timeval = self.getsockopt(libc::SOL_SOCKET, optname)?;
+        let timeout = Duration::new(tval.tv_sec as u64, tval.tv_usec as u32 * 1000);
+        Ok(if timeout.is_zero() {
+            None
+        } else {
+            Some(timeout)
+        })
+    }
+
     /// Try to clone this socket
     pub fn try_clone(&self) -> Result<SctpSocket> {
-        match syscall!(dup(self.0 as _)) {
+        match syscall!(fcntl(self.fd as _, libc::F_DUPFD_CLOEXEC, 0)) {
+            Err(err) => Err(err),
+            Ok(new_sock) => {
+                let sock = SctpSocket {
+                    fd: new_sock as SOCKET,
+                    prefer_modern: AtomicBool::new(self.prefer_modern.load(Ordering::Relaxed)),
+                    recv_limit: self.recv_limit.clone(),
+                };
+                Ok(sock)
+            }
+        }
+    }
+
+    /// Get the type this socket was created with (`SOCK_STREAM` or `SOCK_SEQPACKET`)
+    #[cfg(target_os = "linux")]
+    pub fn sock_type(&self) -> Result<libc::c_int> {
+        self.getsockopt(libc::SOL_SOCKET, libc::SO_TYPE)
+    }
+
+    /// Get the address family this socket was created with
+    #[cfg(target_os = "linux")]
+    pub fn family(&self) -> Result<libc::c_int> {
+        self.getsockopt(libc::SOL_SOCKET, libc::SO_DOMAIN)
+    }
+
+    /// Enable or disable reception of extended errors (`IP_RECVERR` / `IPV6_RECVERR`)
+    /// in the socket error queue
+    #[cfg(target_os = "linux")]
+    pub fn set_recv_err(&self, on: bool) -> Result<()> {
+        let val: libc::c_int = if on { 1 } else { 0 };
+        match self.family()? {
+            AF_INET6 => self.setsockopt(libc::IPPROTO_IPV6, libc::IPV6_RECVERR, &val),
+            _ => self.setsockopt(libc::IPPROTO_IP, libc::IP_RECVERR, &val),
+        }
+    }
+
+    /// Read one extended error from the socket error queue, without blocking.
+    /// Returns `None` if the queue is empty
+    #[cfg(target_os = "linux")]
+    pub fn recv_error(&self) -> Result<Option<SctpExtError>> {
+        let mut data = [0u8; 1];
+        let mut iov = libc::iovec {
+            iov_base: data.as_mut_ptr() as *mut libc::c_void,
+            iov_len: data.len(),
+        };
+        // u64 array to keep the control buffer aligned for cmsghdr
+        let mut control = [0u64; CONTROL_LEN / 8];
+        let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = CONTROL_LEN as _;
+
+        match syscall!(recvmsg(
+            self.fd,
+            &mut msg,
+            libc::MSG_ERRQUEUE | libc::MSG_DONTWAIT
+        )) {
+            Err(ref err) if err.kind() == ErrorKind::WouldBlock => Ok(None),
             Err(err) => Err(err),
-            Ok(new_sock) => Ok(SctpSocket(new_sock as SOCKET)),
+            Ok(_) => {
+                check_ctrunc(&msg)?;
+                Ok(unsafe { errqueue::parse(&msg) })
+            }
+        }
+    }
+
+    /// Block until the socket is readable, or until `token` is triggered in which
+    /// case an `ErrorKind::Interrupted` error carrying a `Cancelled` marker is returned
+    #[cfg(target_os = "linux")]
+    pub fn wait_readable(&self, token: &ShutdownToken) -> Result<()> {
+        let mut fds = [
+            libc::pollfd {
+                fd: self.fd,
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: token.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+        loop {
+            match syscall!(poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1)) {
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+                Ok(_) => break,
+            }
         }
+        if fds[1].revents != 0 {
+            return Err(Cancelled::error());
+        }
+        Ok(())
     }
 }
 
@@ -557,33 +2319,33 @@ impl Write for SctpSocket {
 #[cfg(target_os = "windows")]
 impl AsRawHandle for SctpSocket {
     fn as_raw_handle(&self) -> RawHandle {
-        self.0 as RawHandle
+        self.fd as RawHandle
     }
 }
 
 #[cfg(target_os = "windows")]
 impl FromRawHandle for SctpSocket {
     unsafe fn from_raw_handle(hdl: RawHandle) -> SctpSocket {
-        SctpSocket(hdl as SOCKET)
+        SctpSocket::from_sock(hdl as SOCKET)
     }
 }
 
 #[cfg(target_os = "linux")]
 impl AsRawFd for SctpSocket {
     fn as_raw_fd(&self) -> RawFd {
-        self.0
+        self.fd
     }
 }
 
 #[cfg(target_os = "linux")]
 impl FromRawFd for SctpSocket {
     unsafe fn from_raw_fd(fd: RawFd) -> SctpSocket {
-        SctpSocket(fd)
+        SctpSocket::from_sock(fd)
     }
 }
 
 impl Drop for SctpSocket {
     fn drop(&mut self) {
-        unsafe { closesocket(self.0) };
+        unsafe { closesocket(self.fd) };
     }
 }
@@ -0,0 +1,43 @@
+//! Integration with mio, to drive the sockets from a `mio::Poll`.
+//! The sockets should be put in non-blocking mode with `set_nonblocking` before being
+//! registered
+
+use std::io::Result;
+use std::os::unix::io::AsRawFd;
+
+use mio::unix::SourceFd;
+use mio::{event, Interest, Registry, Token};
+
+use crate::{SctpEndpoint, SctpListener, SctpStream};
+
+macro_rules! impl_source {
+    ($ty: ty) => {
+        impl event::Source for $ty {
+            fn register(
+                &mut self,
+                registry: &Registry,
+                token: Token,
+                interests: Interest,
+            ) -> Result<()> {
+                SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+            }
+
+            fn reregister(
+                &mut self,
+                registry: &Registry,
+                token: Token,
+                interests: Interest,
+            ) -> Result<()> {
+                SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+            }
+
+            fn deregister(&mut self, registry: &Registry) -> Result<()> {
+                SourceFd(&self.as_raw_fd()).deregister(registry)
+            }
+        }
+    };
+}
+
+impl_source!(SctpStream);
+impl_source!(SctpListener);
+impl_source!(SctpEndpoint);
@@ -0,0 +1,189 @@
+//! Association status, as reported by the `SCTP_STATUS` socket option
+
+use std::net::SocketAddr;
+
+use sctp_sys::sctp_assoc_t;
+
+use crate::ffi;
+use crate::mio_unix::to_socket_addr;
+
+/// State of an SCTP association
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssocState {
+    /// No association
+    Empty,
+    /// Association is closed
+    Closed,
+    /// INIT sent, waiting for INIT-ACK
+    CookieWait,
+    /// COOKIE-ECHO sent, waiting for COOKIE-ACK
+    CookieEchoed,
+    /// Association is up and running
+    Established,
+    /// Shutdown requested, waiting for outstanding data to be acknowledged
+    ShutdownPending,
+    /// SHUTDOWN sent
+    ShutdownSent,
+    /// SHUTDOWN received
+    ShutdownReceived,
+    /// SHUTDOWN-ACK sent
+    ShutdownAckSent,
+    /// State value unknown to this crate
+    Unknown(i32),
+}
+
+impl AssocState {
+    /// Convert a raw `sstat_state` value
+    pub fn from_raw(state: i32) -> AssocState {
+        match state {
+            ffi::SCTP_EMPTY => AssocState::Empty,
+            ffi::SCTP_CLOSED => AssocState::Closed,
+            ffi::SCTP_COOKIE_WAIT => AssocState::CookieWait,
+            ffi::SCTP_COOKIE_ECHOED => AssocState::CookieEchoed,
+            ffi::SCTP_ESTABLISHED => AssocState::Established,
+            ffi::SCTP_SHUTDOWN_PENDING => AssocState::ShutdownPending,
+            ffi::SCTP_SHUTDOWN_SENT => AssocState::ShutdownSent,
+            ffi::SCTP_SHUTDOWN_RECEIVED => AssocState::ShutdownReceived,
+            ffi::SCTP_SHUTDOWN_ACK_SENT => AssocState::ShutdownAckSent,
+            other => AssocState::Unknown(other),
+        }
+    }
+}
+
+/// Live status of an association
+#[derive(Debug, Clone)]
+pub struct SctpStatus {
+    /// Association id
+    pub assoc_id: sctp_assoc_t,
+    /// Current state of the association
+    pub state: AssocState,
+    /// Current receive window of the peer
+    pub rwnd: u32,
+    /// Number of unacknowledged DATA chunks
+    pub unacked_data: u16,
+    /// Number of DATA chunks pending receipt
+    pub pending_data: u16,
+    /// Number of negotiated inbound streams
+    pub instreams: u16,
+    /// Number of negotiated outbound streams
+    pub outstreams: u16,
+    /// Size at which user messages are fragmented
+    pub fragmentation_point: u32,
+    /// Primary destination address of the peer, if known
+    pub primary_addr: Option<SocketAddr>,
+    /// Path MTU of the primary destination address
+    pub primary_mtu: u32,
+}
+
+impl SctpStatus {
+    pub(crate) fn from_raw(raw: &ffi::sctp_status) -> SctpStatus {
+        let primary = raw.sstat_primary;
+        let address = primary.spinfo_address;
+        SctpStatus {
+            assoc_id: raw.sstat_assoc_id,
+            state: AssocState::from_raw(raw.sstat_state),
+            rwnd: raw.sstat_rwnd,
+            unacked_data: raw.sstat_unackdata,
+            pending_data: raw.sstat_penddata,
+            instreams: raw.sstat_instrms,
+            outstreams: raw.sstat_outstrms,
+            fragmentation_point: raw.sstat_fragmentation_point,
+            primary_addr: unsafe { to_socket_addr(&address) }.ok(),
+            primary_mtu: primary.spinfo_mtu,
+        }
+    }
+}
+
+/// Statistics of an association, as reported by `SCTP_GET_ASSOC_STATS`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssocStats {
+    /// Association id
+    pub assoc_id: sctp_assoc_t,
+    /// Largest RTO observed since the previous query, in milliseconds. The kernel resets it
+    /// on each query
+    pub max_rto: u64,
+    /// Peer address the largest RTO was observed on, if any
+    pub max_rto_addr: Option<SocketAddr>,
+    /// Number of SACKs received
+    pub sacks_received: u64,
+    /// Number of SACKs sent
+    pub sacks_sent: u64,
+    /// Number of packets sent
+    pub packets_sent: u64,
+    /// Number of packets received
+    pub packets_received: u64,
+    /// Number of retransmitted DATA chunks
+    pub retransmitted_chunks: u64,
+    /// Number of TSNs received out of sequence
+    pub out_of_seq_tsns: u64,
+    /// Number of duplicate DATA chunks received
+    pub duplicate_chunks: u64,
+    /// Number of gap acknowledgements received
+    pub gap_acks: u64,
+    /// Number of unordered DATA chunks sent
+    pub unordered_chunks_sent: u64,
+    /// Number of unordered DATA chunks received
+    pub unordered_chunks_received: u64,
+    /// Number of ordered DATA chunks sent
+    pub ordered_chunks_sent: u64,
+    /// Number of ordered DATA chunks received
+    pub ordered_chunks_received: u64,
+    /// Number of control chunks sent
+    pub ctrl_chunks_sent: u64,
+    /// Number of control chunks received
+    pub ctrl_chunks_received: u64,
+}
+
+impl AssocStats {
+    pub(crate) fn from_raw(raw: &ffi::sctp_assoc_stats) -> AssocStats {
+        AssocStats {
+            assoc_id: raw.sas_assoc_id,
+            max_rto: raw.sas_maxrto,
+            max_rto_addr: unsafe { to_socket_addr(&raw.sas_obs_rto_ipaddr) }.ok(),
+            sacks_received: raw.sas_isacks,
+            sacks_sent: raw.sas_osacks,
+            packets_sent: raw.sas_opackets,
+            packets_received: raw.sas_ipackets,
+            retransmitted_chunks: raw.sas_rtxchunks,
+            out_of_seq_tsns: raw.sas_outofseqtsns,
+            duplicate_chunks: raw.sas_idupchunks,
+            gap_acks: raw.sas_gapcnt,
+            unordered_chunks_sent: raw.sas_ouodchunks,
+            unordered_chunks_received: raw.sas_iuodchunks,
+            ordered_chunks_sent: raw.sas_oodchunks,
+            ordered_chunks_received: raw.sas_iodchunks,
+            ctrl_chunks_sent: raw.sas_octrlchunks,
+            ctrl_chunks_received: raw.sas_ictrlchunks,
+        }
+    }
+}
+
+/// Best-effort state of a single stream of an association, returned by `stream_state`.
+///
+/// Linux doesn't expose stream sequence numbers to userspace: the SSN fields are only
+/// filled on stacks providing them, and are `None` on Linux
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamState {
+    /// Stream id
+    pub stream: u16,
+    /// Whether the stream is one of the negotiated outbound streams
+    pub outbound: bool,
+    /// Whether the stream is one of the negotiated inbound streams
+    pub inbound: bool,
+    /// Next SSN assigned to an ordered message sent on this stream, if available
+    pub next_outbound_ssn: Option<u16>,
+    /// Next SSN expected for an ordered message received on this stream, if available
+    pub next_inbound_ssn: Option<u16>,
+}
+
+impl StreamState {
+    pub(crate) fn from_status(status: &SctpStatus, stream: u16) -> StreamState {
+        StreamState {
+            stream,
+            outbound: stream < status.outstreams,
+            inbound: stream < status.instreams,
+            next_outbound_ssn: None,
+            next_inbound_ssn: None,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Syscall-level tracing hooks, for diagnostics at the FFI boundary.
+//! Only available with the `trace` cargo feature
+
+use std::io::Error;
+use std::sync::{Arc, RwLock};
+
+type TraceHook = Arc<dyn Fn(&SyscallTrace) + Send + Sync>;
+
+static HOOK: RwLock<Option<TraceHook>> = RwLock::new(None);
+
+/// Description of a system call made by this crate, passed to the trace hook
+#[derive(Debug, Clone)]
+pub struct SyscallTrace {
+    /// Name of the called function (`sctp_sendmsg`, `connectx`, ...)
+    pub op: &'static str,
+    /// Arguments of the call, as written in the source code
+    pub args: &'static str,
+    /// Raw value returned by the call
+    pub result: isize,
+    /// OS error code, if the call failed
+    pub errno: Option<i32>,
+}
+
+/// Set the hook invoked after each system call made by this crate, replacing the previous one.
+/// The hook is global to the process, and runs on the thread which made the call
+pub fn set_trace_hook<F>(hook: F)
+where
+    F: Fn(&SyscallTrace) + Send + Sync + 'static,
+{
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(hook));
+}
+
+/// Remove the hook set with `set_trace_hook`
+pub fn clear_trace_hook() {
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+pub(crate) fn emit(op: &'static str, args: &'static str, result: isize, error: Option<&Error>) {
+    // Clone the hook out of the lock, so it may itself set or clear the hook
+    let hook = HOOK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(hook) = hook {
+        hook(&SyscallTrace {
+            op,
+            args,
+            result,
+            errno: error.and_then(Error::raw_os_error),
+        });
+    }
+}